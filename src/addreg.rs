@@ -0,0 +1,269 @@
+use crate::Inf;
+use crate::error::MalformedEntryError;
+use crate::section::{Entry, Section};
+
+/// A row of an add-registry section, referenced by an `AddReg` directive.
+///
+/// Each row has the form `reg-root,[subkey],[value-entry-name],[flags],[value][,value...]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AddRegRow {
+    /// The registry root the subkey is relative to.
+    pub root: RegRoot,
+    /// The key to add, relative to `root`.
+    pub subkey: Option<String>,
+    /// The name of the value to add; `None` for the default value of the key.
+    pub value_name: Option<String>,
+    /// Flags describing the type of the value and how it is written.
+    pub flags: AddRegFlags,
+    /// The value to write, split into its comma-separated fields (e.g. the strings of a
+    /// `REG_MULTI_SZ` value or the bytes of a `REG_BINARY` value).
+    pub values: Vec<String>,
+}
+
+/// The registry root of an [`AddRegRow`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RegRoot {
+    /// `HKCR`, short for `HKEY_CLASSES_ROOT`.
+    ClassesRoot,
+    /// `HKCU`, short for `HKEY_CURRENT_USER`.
+    CurrentUser,
+    /// `HKLM`, short for `HKEY_LOCAL_MACHINE`.
+    LocalMachine,
+    /// `HKU`, short for `HKEY_USERS`.
+    Users,
+    /// `HKR`, the key associated with the device or service being installed.
+    Relative,
+}
+
+impl RegRoot {
+    fn parse(root: &str) -> Option<Self> {
+        [
+            ("HKCR", Self::ClassesRoot),
+            ("HKCU", Self::CurrentUser),
+            ("HKLM", Self::LocalMachine),
+            ("HKU", Self::Users),
+            ("HKR", Self::Relative),
+        ]
+        .into_iter()
+        .find_map(|(name, root_key)| root.eq_ignore_ascii_case(name).then_some(root_key))
+    }
+}
+
+/// The `flags` field of an [`AddRegRow`], written in hexadecimal (`0x00010001`) or decimal.
+///
+/// The bits under `0xFFFF0001` hold the type of the value, one of the `TYPE_*` constants (or
+/// [`AddRegFlags::BINVALUETYPE`]), which are enumerated values rather than independent bits.
+/// Compare them through [`AddRegFlags::value_type`] instead of testing their bits.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct AddRegFlags(pub u32);
+
+impl AddRegFlags {
+    /// The bits that hold the type of the value.
+    const TYPE_MASK: u32 = 0xFFFF_0001;
+
+    /// `FLG_ADDREG_TYPE_SZ`: the value is a `REG_SZ`, the type used when no other is given.
+    pub const TYPE_SZ: Self = Self(0x0000_0000);
+    /// `FLG_ADDREG_BINVALUETYPE`, also known as `FLG_ADDREG_TYPE_BINARY`: the value is a
+    /// `REG_BINARY`.
+    pub const BINVALUETYPE: Self = Self(0x0000_0001);
+    /// `FLG_ADDREG_NOCLOBBER`: an existing value is not replaced.
+    pub const NOCLOBBER: Self = Self(0x0000_0002);
+    /// `FLG_ADDREG_DELVAL`: the value is deleted instead of added.
+    pub const DELVAL: Self = Self(0x0000_0004);
+    /// `FLG_ADDREG_APPEND`: the value is appended to an existing `REG_MULTI_SZ` value.
+    pub const APPEND: Self = Self(0x0000_0008);
+    /// `FLG_ADDREG_KEYONLY`: only the subkey is created; the value is ignored.
+    pub const KEYONLY: Self = Self(0x0000_0010);
+    /// `FLG_ADDREG_OVERWRITEONLY`: the value is only written if it already exists.
+    pub const OVERWRITEONLY: Self = Self(0x0000_0020);
+    /// `FLG_ADDREG_64BITKEY`: the key is written to the 64-bit registry.
+    pub const KEY64: Self = Self(0x0000_1000);
+    /// `FLG_ADDREG_KEYONLY_COMMON`: like [`AddRegFlags::KEYONLY`], shared with `DelReg`.
+    pub const KEYONLY_COMMON: Self = Self(0x0000_2000);
+    /// `FLG_ADDREG_32BITKEY`: the key is written to the 32-bit registry.
+    pub const KEY32: Self = Self(0x0000_4000);
+    /// `FLG_ADDREG_TYPE_MULTI_SZ`: the value is a `REG_MULTI_SZ`.
+    pub const TYPE_MULTI_SZ: Self = Self(0x0001_0000);
+    /// `FLG_ADDREG_TYPE_EXPAND_SZ`: the value is a `REG_EXPAND_SZ`.
+    pub const TYPE_EXPAND_SZ: Self = Self(0x0002_0000);
+    /// `FLG_ADDREG_TYPE_DWORD`: the value is a `REG_DWORD`.
+    pub const TYPE_DWORD: Self = Self(0x0001_0001);
+    /// `FLG_ADDREG_TYPE_NONE`: the value is a `REG_NONE`.
+    pub const TYPE_NONE: Self = Self(0x0002_0001);
+
+    /// Returns the type of the value, which can be compared with the `TYPE_*` constants.
+    #[must_use]
+    pub fn value_type(self) -> Self {
+        Self(self.0 & Self::TYPE_MASK)
+    }
+
+    /// Returns `true` if every flag set in `other` is also set in `self`.
+    ///
+    /// Only the bits outside the value type are tested bit by bit. If `other` has a value type
+    /// other than [`AddRegFlags::TYPE_SZ`], `self` must have the same one, so
+    /// `TYPE_DWORD.contains(TYPE_MULTI_SZ)` is `false` even though every bit of the latter is set
+    /// in the former.
+    #[must_use]
+    pub fn contains(self, other: Self) -> bool {
+        let flags = other.0 & !Self::TYPE_MASK;
+
+        self.0 & flags == flags
+            && (other.value_type() == Self::TYPE_SZ || self.value_type() == other.value_type())
+    }
+
+    fn parse(flags: &str) -> Option<Self> {
+        let flags = flags.trim();
+
+        if flags.is_empty() {
+            return Some(Self::default());
+        }
+
+        let flags = match flags
+            .strip_prefix("0x")
+            .or_else(|| flags.strip_prefix("0X"))
+        {
+            Some(hex) => u32::from_str_radix(hex, 16),
+            None => flags.parse::<u32>(),
+        };
+
+        flags.ok().map(Self)
+    }
+}
+
+impl Inf {
+    /// Returns the rows of the add-registry section named `section`.
+    ///
+    /// Rows that have a key, an unknown registry root, or flags that are not a number are
+    /// skipped. Use [`Inf::try_add_reg_rows`] to reject them instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use inf::{AddRegFlags, Inf, RegRoot};
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let inf = Inf::from_bytes(b"[Device.AddReg]\nHKR,,Enabled,0x00010001,1")?;
+    /// let rows = inf.add_reg_rows("Device.AddReg");
+    /// assert_eq!(rows[0].root, RegRoot::Relative);
+    /// assert_eq!(rows[0].flags.value_type(), AddRegFlags::TYPE_DWORD);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn add_reg_rows(&self, section: &str) -> Vec<AddRegRow> {
+        self.get(section)
+            .map(Section::entries)
+            .unwrap_or_default()
+            .iter()
+            .filter_map(parse_add_reg_row)
+            .collect()
+    }
+
+    /// Returns the rows of the add-registry section named `section`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`MalformedEntryError`] for the first row that has a key, an unknown registry
+    /// root, or flags that are not a number.
+    pub fn try_add_reg_rows(&self, section: &str) -> Result<Vec<AddRegRow>, MalformedEntryError> {
+        let Some(section) = self.get(section) else {
+            return Ok(Vec::new());
+        };
+
+        section
+            .entries()
+            .iter()
+            .map(|entry| {
+                parse_add_reg_row(entry)
+                    .ok_or_else(|| MalformedEntryError::new(section.name(), entry))
+            })
+            .collect()
+    }
+}
+
+fn parse_add_reg_row(entry: &Entry) -> Option<AddRegRow> {
+    let Entry::Value(value) = entry else {
+        return None;
+    };
+    let fields = value.as_slice();
+    let field = |i: usize| fields.get(i).filter(|field| !field.is_empty()).cloned();
+
+    Some(AddRegRow {
+        root: RegRoot::parse(fields.first()?)?,
+        subkey: field(1),
+        value_name: field(2),
+        flags: fields
+            .get(3)
+            .map_or(Some(AddRegFlags::default()), |flags| {
+                AddRegFlags::parse(flags)
+            })?,
+        values: fields.get(4..).unwrap_or_default().to_vec(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add_reg_rows() {
+        let buffer = b"\
+            [DDInstall.AddReg]\n\
+            HKR,,DeviceCharacteristics,0x10001,0x0100\n\
+            hklm,Software\\Contoso,Paths,0x00010000,\"C:\\a\",\"C:\\b\"\n\
+            HKR,Parameters\n\
+            HKXX,,Bad,0,1\n\
+            HKR,,Bad,notaflag,1\
+        ";
+        let inf = Inf::from_bytes(buffer).expect("failed to parse hardcoded INF file");
+
+        assert_eq!(
+            inf.add_reg_rows("ddinstall.addreg"),
+            vec![
+                AddRegRow {
+                    root: RegRoot::Relative,
+                    subkey: None,
+                    value_name: Some("DeviceCharacteristics".to_owned()),
+                    flags: AddRegFlags::TYPE_DWORD,
+                    values: vec!["0x0100".to_owned()],
+                },
+                AddRegRow {
+                    root: RegRoot::LocalMachine,
+                    subkey: Some("Software\\Contoso".to_owned()),
+                    value_name: Some("Paths".to_owned()),
+                    flags: AddRegFlags::TYPE_MULTI_SZ,
+                    values: vec!["C:\\a".to_owned(), "C:\\b".to_owned()],
+                },
+                AddRegRow {
+                    root: RegRoot::Relative,
+                    subkey: Some("Parameters".to_owned()),
+                    value_name: None,
+                    flags: AddRegFlags::default(),
+                    values: vec![],
+                },
+            ]
+        );
+        assert!(!AddRegFlags::TYPE_MULTI_SZ.contains(AddRegFlags::TYPE_DWORD));
+        assert!(!AddRegFlags::TYPE_DWORD.contains(AddRegFlags::TYPE_MULTI_SZ));
+        assert!(!AddRegFlags::TYPE_NONE.contains(AddRegFlags::TYPE_EXPAND_SZ));
+        assert!(!AddRegFlags::TYPE_DWORD.contains(AddRegFlags::BINVALUETYPE));
+
+        let flags = AddRegFlags(AddRegFlags::TYPE_DWORD.0 | AddRegFlags::NOCLOBBER.0);
+        assert_eq!(flags.value_type(), AddRegFlags::TYPE_DWORD);
+        assert_eq!(AddRegFlags::NOCLOBBER.value_type(), AddRegFlags::TYPE_SZ);
+        assert!(flags.contains(AddRegFlags::TYPE_DWORD));
+        assert!(flags.contains(AddRegFlags::NOCLOBBER));
+        assert!(!flags.contains(AddRegFlags::APPEND));
+
+        let err = inf
+            .try_add_reg_rows("DDInstall.AddReg")
+            .expect_err("expected a malformed entry");
+        assert_eq!(
+            err.entry()
+                .value()
+                .as_list()
+                .map(|fields| fields[0].as_str()),
+            Some("HKXX")
+        );
+    }
+}
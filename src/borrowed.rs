@@ -0,0 +1,269 @@
+use std::borrow::Cow;
+
+use crate::Inf;
+use crate::error::ParseError;
+use crate::options::ParserOptions;
+use crate::parser::Parser;
+use crate::section::{Entry, Section, Value};
+
+/// A parsed INF file that borrows its names and values from the text it was parsed from.
+///
+/// Names and values are only allocated when they had to be un-escaped (e.g. `""` within
+/// quotes) or when they span multiple lines. This avoids an allocation per value for large
+/// files, at the cost of keeping the source text alive. Use [`InfRef::into_owned`] to convert
+/// to an [`Inf`].
+///
+/// # Examples
+///
+/// ```
+/// use std::borrow::Cow;
+/// use inf::{InfRef, ValueRef};
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let text = "[Version]\nSignature=\"$Chicago$\"";
+/// let inf = InfRef::parse(text)?;
+/// let entry = &inf.get("version").unwrap().entries()[0];
+/// assert!(matches!(entry.value(), ValueRef::Raw(Cow::Borrowed("$Chicago$"))));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InfRef<'a> {
+    sections: Vec<SectionRef<'a>>,
+}
+
+impl<'a> InfRef<'a> {
+    /// Parses `text` as the contents of an INF file.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ParseError`] if the text is not a valid INF file.
+    pub fn parse(text: &'a str) -> Result<Self, ParseError> {
+        Self::parse_with_options(text, &ParserOptions::default())
+    }
+
+    /// Parses `text` as the contents of an INF file, using `options` to control parsing.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ParseError`] if the text is not a valid INF file.
+    pub fn parse_with_options(text: &'a str, options: &ParserOptions) -> Result<Self, ParseError> {
+        Parser::new(text, options.clone()).parse()
+    }
+
+    pub(crate) fn from_sections(sections: Vec<SectionRef<'a>>) -> Self {
+        Self { sections }
+    }
+
+    #[must_use]
+    pub fn sections(&self) -> &[SectionRef<'a>] {
+        &self.sections
+    }
+
+    /// Returns the first section whose name matches `name`, ignoring ASCII case.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&SectionRef<'a>> {
+        self.sections
+            .iter()
+            .find(|section| name.eq_ignore_ascii_case(section.name()))
+    }
+
+    /// Converts into an [`Inf`] that owns all of its names and values.
+    #[must_use]
+    pub fn into_owned(self) -> Inf {
+        Inf {
+            sections: self
+                .sections
+                .into_iter()
+                .map(SectionRef::into_owned)
+                .collect(),
+        }
+    }
+}
+
+/// A borrowed counterpart to [`Section`].
+#[derive(Debug, Clone)]
+pub struct SectionRef<'a> {
+    name: Cow<'a, str>,
+    entries: Vec<EntryRef<'a>>,
+    duplicates: usize,
+}
+
+impl<'a> SectionRef<'a> {
+    #[must_use]
+    pub(crate) fn new(name: Cow<'a, str>, entries: Vec<EntryRef<'a>>) -> Self {
+        Self {
+            name,
+            entries,
+            duplicates: 0,
+        }
+    }
+
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    #[must_use]
+    pub fn entries(&self) -> &[EntryRef<'a>] {
+        &self.entries
+    }
+
+    /// See [`Section::duplicate_count`].
+    #[must_use]
+    pub fn duplicate_count(&self) -> usize {
+        self.duplicates
+    }
+
+    pub(crate) fn push(&mut self, value: EntryRef<'a>) {
+        self.entries.push(value);
+    }
+
+    /// Records that another header with this name was merged into the section.
+    pub(crate) fn add_duplicate(&mut self) {
+        self.duplicates += 1;
+    }
+
+    /// Converts into a [`Section`] that owns all of its names and values.
+    #[must_use]
+    pub fn into_owned(self) -> Section {
+        Section::new(
+            self.name.into_owned(),
+            self.entries.into_iter().map(EntryRef::into_owned).collect(),
+        )
+        .with_duplicates(self.duplicates)
+    }
+}
+
+impl PartialEq for SectionRef<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && self.entries == other.entries
+    }
+}
+
+impl Eq for SectionRef<'_> {}
+
+/// A borrowed counterpart to [`Entry`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EntryRef<'a> {
+    Item(Cow<'a, str>, ValueRef<'a>),
+    Value(ValueRef<'a>),
+}
+
+impl EntryRef<'_> {
+    /// Returns the key of an [`EntryRef::Item`], or `None` for a value-only entry.
+    #[must_use]
+    pub fn key(&self) -> Option<&str> {
+        match self {
+            Self::Item(key, _) => Some(key),
+            Self::Value(_) => None,
+        }
+    }
+
+    /// Returns the value of the entry, regardless of whether it has a key.
+    #[must_use]
+    pub fn value(&self) -> &ValueRef<'_> {
+        match self {
+            Self::Item(_, value) | Self::Value(value) => value,
+        }
+    }
+
+    /// Converts into an [`Entry`] that owns all of its names and values.
+    #[must_use]
+    pub fn into_owned(self) -> Entry {
+        match self {
+            Self::Item(key, value) => Entry::Item(key.into_owned(), value.into_owned()),
+            Self::Value(value) => Entry::Value(value.into_owned()),
+        }
+    }
+
+    /// Detaches the entry from the text it borrows from, allocating where necessary.
+    pub(crate) fn into_static(self) -> EntryRef<'static> {
+        match self {
+            Self::Item(key, value) => {
+                EntryRef::Item(Cow::Owned(key.into_owned()), value.into_static())
+            }
+            Self::Value(value) => EntryRef::Value(value.into_static()),
+        }
+    }
+}
+
+/// A borrowed counterpart to [`Value`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValueRef<'a> {
+    Raw(Cow<'a, str>),
+    List(Vec<Cow<'a, str>>),
+}
+
+impl ValueRef<'_> {
+    /// Converts into a [`Value`] that owns all of its strings.
+    #[must_use]
+    pub fn into_owned(self) -> Value {
+        match self {
+            Self::Raw(value) => Value::Raw(value.into_owned()),
+            Self::List(values) => Value::List(values.into_iter().map(Cow::into_owned).collect()),
+        }
+    }
+
+    fn into_static(self) -> ValueRef<'static> {
+        match self {
+            Self::Raw(value) => ValueRef::Raw(Cow::Owned(value.into_owned())),
+            Self::List(values) => ValueRef::List(
+                values
+                    .into_iter()
+                    .map(|value| Cow::Owned(value.into_owned()))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn values_borrow_unless_unescaped() {
+        let text = "\
+            [Section]\n\
+            plain = value\n\
+            escaped = \"say \"\"hi\"\"\"\n\
+            joined = a,\\\n\
+            b\
+        ";
+        let inf = InfRef::parse(text).expect("failed to parse hardcoded INF file");
+        let entries = inf.get("Section").unwrap().entries();
+
+        assert!(matches!(
+            &entries[0],
+            EntryRef::Item(
+                Cow::Borrowed("plain"),
+                ValueRef::Raw(Cow::Borrowed("value"))
+            )
+        ));
+        assert!(matches!(
+            &entries[1],
+            EntryRef::Item(Cow::Borrowed("escaped"), ValueRef::Raw(Cow::Owned(value)))
+                if value == "say \"hi\""
+        ));
+        assert!(matches!(
+            entries[2].value(),
+            ValueRef::List(values) if values.iter().all(|value| matches!(value, Cow::Owned(_)))
+        ));
+    }
+
+    #[test]
+    fn into_owned_matches_inf() {
+        let text = "\
+            [Section]\n\
+            key = value1,\"value2\"\n\
+            value\
+        ";
+        let inf = InfRef::parse(text).expect("failed to parse hardcoded INF file");
+
+        assert_eq!(
+            inf.into_owned(),
+            Inf::from_bytes(text.as_bytes()).expect("failed to parse hardcoded INF file")
+        );
+    }
+}
@@ -0,0 +1,471 @@
+use crate::error::ParseError;
+use crate::options::{AnsiCodePage, ParserOptions};
+use crate::{BOM_BE, BOM_CHAR, BOM_LE};
+
+/// The text of an INF file after decoding, along with where each character came from in the
+/// original bytes.
+///
+/// [`Inf::from_bytes`](crate::Inf::from_bytes) discards the original encoding once the file is
+/// decoded. Decode the file with this type instead when positions within the decoded text
+/// need to be mapped back to the file on disk, such as to highlight an entry in an editor.
+/// The text is identical to what [`Inf::from_bytes_with_options`] parses.
+///
+/// [`Inf::from_bytes_with_options`]: crate::Inf::from_bytes_with_options
+///
+/// # Examples
+///
+/// ```
+/// use inf::{DecodedText, InfRef, ParserOptions};
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let buffer = b"\xFF\xFE[\x00A\x00]\x00";
+/// let options = ParserOptions::default();
+/// let decoded = DecodedText::new(buffer, &options);
+/// let inf = InfRef::parse_with_options(decoded.text(), &options)?;
+///
+/// assert_eq!(inf.sections()[0].name(), "A");
+/// assert_eq!(decoded.source_len_bytes(), 8);
+/// assert_eq!(decoded.byte_offset(1), Some(4));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodedText {
+    text: String,
+    // The byte offset in the original buffer of each character of `text`.
+    offsets: Vec<usize>,
+    source_len: usize,
+    warnings: Vec<DecodeWarning>,
+}
+
+/// A sign that a file may have been decoded with the wrong encoding.
+///
+/// Warnings do not stop the file from being decoded or parsed; see [`DecodedText::warnings`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeWarning {
+    /// A UTF-16 LE Byte Order Mark (`FF FE`) was found after the start of a file decoded with
+    /// an ANSI code page, which suggests UTF-16 content was pasted into an ANSI file.
+    EmbeddedBom {
+        /// The byte offset of the Byte Order Mark in the original buffer.
+        offset: usize,
+    },
+    /// A UTF-16 code unit that is not part of a valid character was substituted (see
+    /// [`ParserOptions::replacement_char`]).
+    InvalidUtf16 {
+        /// The byte offset of the code unit in the original buffer.
+        offset: usize,
+    },
+}
+
+impl DecodedText {
+    /// Decodes `buffer` the same way as [`Inf::from_bytes_with_options`], keeping track of
+    /// the byte offset of each decoded character.
+    ///
+    /// [`ParserOptions::strict_utf16`] is ignored: invalid UTF-16 is always substituted and
+    /// reported through [`DecodedText::warnings`] instead.
+    ///
+    /// [`Inf::from_bytes_with_options`]: crate::Inf::from_bytes_with_options
+    #[must_use]
+    pub fn new(buffer: &[u8], options: &ParserOptions) -> Self {
+        let mut decoded = Self {
+            text: String::with_capacity(buffer.len()),
+            offsets: Vec::with_capacity(buffer.len()),
+            source_len: buffer.len(),
+            warnings: Vec::new(),
+        };
+
+        // Every invalid code unit is substituted rather than rejected, so this never fails.
+        _ = decode(buffer, options, &mut decoded);
+
+        if !buffer.starts_with(&BOM_LE) && !buffer.starts_with(&BOM_BE) {
+            decoded.warnings.extend(
+                buffer
+                    .windows(BOM_LE.len())
+                    .enumerate()
+                    .filter(|&(_, bytes)| bytes == BOM_LE)
+                    .map(|(offset, _)| DecodeWarning::EmbeddedBom { offset }),
+            );
+        }
+
+        decoded
+    }
+
+    /// Returns the decoded text.
+    #[must_use]
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Returns the length of the original buffer, in bytes.
+    #[must_use]
+    pub fn source_len_bytes(&self) -> usize {
+        self.source_len
+    }
+
+    /// Returns signs that the file may have been decoded with the wrong encoding, in the order
+    /// they appear in the original buffer.
+    #[must_use]
+    pub fn warnings(&self) -> &[DecodeWarning] {
+        &self.warnings
+    }
+
+    /// Returns the byte offset in the original buffer of the character at `char_index` in the
+    /// decoded text.
+    ///
+    /// The offset is that of the first byte the character was decoded from, which for a
+    /// replacement character is the start of the invalid sequence. Passing the number of
+    /// characters in the text returns [`DecodedText::source_len_bytes`], so that the end of a
+    /// range can be mapped too. Returns `None` for any index past that.
+    #[must_use]
+    pub fn byte_offset(&self, char_index: usize) -> Option<usize> {
+        self.offsets
+            .get(char_index)
+            .copied()
+            .or_else(|| (char_index == self.offsets.len()).then_some(self.source_len))
+    }
+}
+
+/// Receives the characters produced while decoding an INF file.
+pub(crate) trait DecodeSink {
+    /// Appends `c`, which was decoded from the bytes starting at `offset`.
+    fn push_char(&mut self, c: char, offset: usize);
+
+    /// Handles a UTF-16 code unit at `offset` that is not part of a valid character, either by
+    /// substituting it or by rejecting the data.
+    fn push_invalid(&mut self, offset: usize, options: &ParserOptions) -> Result<(), ParseError>;
+}
+
+/// Collects the text alone, as parsed by [`Inf`](crate::Inf).
+impl DecodeSink for String {
+    fn push_char(&mut self, c: char, _offset: usize) {
+        // Stray BOMs are not meaningful content, so they are dropped.
+        if c != BOM_CHAR {
+            self.push(c);
+        }
+    }
+
+    fn push_invalid(&mut self, offset: usize, options: &ParserOptions) -> Result<(), ParseError> {
+        if options.strict_utf16 {
+            return Err(ParseError::InvalidUtf16 { offset });
+        }
+
+        self.extend(options.replacement_char);
+        Ok(())
+    }
+}
+
+impl DecodeSink for DecodedText {
+    fn push_char(&mut self, c: char, offset: usize) {
+        // Stray BOMs are dropped, as they are when parsing.
+        if c != BOM_CHAR {
+            self.text.push(c);
+            self.offsets.push(offset);
+        }
+    }
+
+    fn push_invalid(&mut self, offset: usize, options: &ParserOptions) -> Result<(), ParseError> {
+        self.warnings.push(DecodeWarning::InvalidUtf16 { offset });
+
+        if let Some(c) = options.replacement_char {
+            self.push_char(c, offset);
+        }
+
+        Ok(())
+    }
+}
+
+/// Decodes `data` as UTF-16 if it starts with a BOM, otherwise using the ANSI code page.
+pub(crate) fn decode(
+    data: &[u8],
+    options: &ParserOptions,
+    sink: &mut impl DecodeSink,
+) -> Result<(), ParseError> {
+    // INF files must be saved with UTF-16 LE or ANSI file encodings. ANSI encodings never
+    // begin with a BOM, so the BOM being present strongly suggests that the data was encoded
+    // with UTF-16, in the byte order the BOM is written in.
+    if let Some((mut decoder, data)) = Utf16Decoder::from_bom(data) {
+        decoder.push(data, options, sink)?;
+        decoder.finish(options, sink)
+    } else {
+        decode_ansi(data, options.ansi_code_page, sink);
+        Ok(())
+    }
+}
+
+/// Decodes UTF-16 data that may be split at any byte, such as when it is read in chunks.
+pub(crate) struct Utf16Decoder {
+    big_endian: bool,
+    /// The first byte of a code unit that was split from its second byte.
+    pending_byte: Option<u8>,
+    /// A high surrogate that has not been paired with the following code unit yet.
+    pending_surrogate: Option<u16>,
+    /// The byte offset in the original data of the next code unit to be decoded, including
+    /// the BOM.
+    offset: usize,
+}
+
+impl Utf16Decoder {
+    /// Creates a decoder for the byte order given by the BOM at the start of `data`, returning
+    /// it with the data that follows the BOM, or `None` if `data` does not start with one.
+    pub(crate) fn from_bom(data: &[u8]) -> Option<(Self, &[u8])> {
+        let (big_endian, rest) = if let Some(rest) = data.strip_prefix(&BOM_LE) {
+            (false, rest)
+        } else {
+            (true, data.strip_prefix(&BOM_BE)?)
+        };
+
+        let decoder = Self {
+            big_endian,
+            pending_byte: None,
+            pending_surrogate: None,
+            offset: BOM_LE.len(),
+        };
+
+        Some((decoder, rest))
+    }
+
+    fn unit(&self, bytes: [u8; 2]) -> u16 {
+        if self.big_endian {
+            u16::from_be_bytes(bytes)
+        } else {
+            u16::from_le_bytes(bytes)
+        }
+    }
+
+    pub(crate) fn push(
+        &mut self,
+        mut data: &[u8],
+        options: &ParserOptions,
+        sink: &mut impl DecodeSink,
+    ) -> Result<(), ParseError> {
+        if let Some(first) = self.pending_byte.take() {
+            let Some((&second, rest)) = data.split_first() else {
+                self.pending_byte = Some(first);
+                return Ok(());
+            };
+            self.push_unit(self.unit([first, second]), options, sink)?;
+            data = rest;
+        }
+
+        let mut units = data.chunks_exact(2);
+
+        for unit in &mut units {
+            self.push_unit(self.unit([unit[0], unit[1]]), options, sink)?;
+        }
+
+        self.pending_byte = units.remainder().first().copied();
+        Ok(())
+    }
+
+    fn push_unit(
+        &mut self,
+        unit: u16,
+        options: &ParserOptions,
+        sink: &mut impl DecodeSink,
+    ) -> Result<(), ParseError> {
+        if let Some(high) = self.pending_surrogate.take() {
+            if let Some(Ok(c)) = char::decode_utf16([high, unit]).next() {
+                sink.push_char(c, self.offset);
+                self.offset += 4;
+                return Ok(());
+            }
+
+            // The high surrogate is invalid on its own; `unit` is decoded separately below.
+            self.push_invalid(options, sink)?;
+        }
+
+        match char::decode_utf16([unit]).next() {
+            Some(Ok(c)) => {
+                sink.push_char(c, self.offset);
+                self.offset += 2;
+            }
+            _ if (0xD800..0xDC00).contains(&unit) => self.pending_surrogate = Some(unit),
+            _ => self.push_invalid(options, sink)?,
+        }
+
+        Ok(())
+    }
+
+    fn push_invalid(
+        &mut self,
+        options: &ParserOptions,
+        sink: &mut impl DecodeSink,
+    ) -> Result<(), ParseError> {
+        sink.push_invalid(self.offset, options)?;
+        self.offset += 2;
+        Ok(())
+    }
+
+    /// Decodes anything still pending at the end of the data. A trailing odd byte is ignored.
+    pub(crate) fn finish(
+        mut self,
+        options: &ParserOptions,
+        sink: &mut impl DecodeSink,
+    ) -> Result<(), ParseError> {
+        if self.pending_surrogate.take().is_some() {
+            self.push_invalid(options, sink)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Decodes data that was saved with an ANSI encoding using the given code page.
+pub(crate) fn decode_ansi(data: &[u8], code_page: AnsiCodePage, sink: &mut impl DecodeSink) {
+    let encoding = match code_page {
+        AnsiCodePage::Utf8 => {
+            let mut offset = 0;
+
+            // Matches `String::from_utf8_lossy`, which replaces each invalid sequence with a
+            // single replacement character.
+            for chunk in data.utf8_chunks() {
+                for (i, c) in chunk.valid().char_indices() {
+                    sink.push_char(c, offset + i);
+                }
+
+                offset += chunk.valid().len();
+
+                if !chunk.invalid().is_empty() {
+                    sink.push_char(char::REPLACEMENT_CHARACTER, offset);
+                    offset += chunk.invalid().len();
+                }
+            }
+
+            return;
+        }
+        AnsiCodePage::Latin1 => {
+            for (i, &b) in data.iter().enumerate() {
+                sink.push_char(char::from(b), i);
+            }
+
+            return;
+        }
+        AnsiCodePage::Windows1252 => encoding_rs::WINDOWS_1252,
+        AnsiCodePage::ShiftJis => encoding_rs::SHIFT_JIS,
+    };
+
+    // Feed the decoder one byte at a time so that each character can be attributed to the
+    // byte that started its sequence.
+    let mut decoder = encoding.new_decoder_without_bom_handling();
+    let mut chars = String::new();
+    let mut start = 0;
+
+    for (i, byte) in data.iter().enumerate() {
+        chars.clear();
+        chars.reserve(decoder.max_utf8_buffer_length(1).unwrap_or(16));
+        _ = decoder.decode_to_string(std::slice::from_ref(byte), &mut chars, i + 1 == data.len());
+
+        if !chars.is_empty() {
+            for c in chars.chars() {
+                sink.push_char(c, start);
+            }
+
+            start = i + 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn byte_offsets() {
+        let utf16 = [BOM_LE.as_slice(), b"a\x00\x3D\xD8\x00\xDE\x00\xD8b\x00"].concat();
+        let utf16_be = [BOM_BE.as_slice(), b"\x00a\xD8\x3D\xDE\x00\xD8\x00\x00b"].concat();
+        let cases: [(&[u8], AnsiCodePage, &str, &[usize]); 6] = [
+            (
+                &utf16,
+                AnsiCodePage::Utf8,
+                "a\u{1F600}\u{FFFD}b",
+                &[2, 4, 8, 10],
+            ),
+            (
+                &utf16_be,
+                AnsiCodePage::Utf8,
+                "a\u{1F600}\u{FFFD}b",
+                &[2, 4, 8, 10],
+            ),
+            (
+                b"a\xC3\xA9\xFFb",
+                AnsiCodePage::Utf8,
+                "a\u{E9}\u{FFFD}b",
+                &[0, 1, 3, 4],
+            ),
+            (b"a\xEF\xBB\xBFb", AnsiCodePage::Utf8, "ab", &[0, 4]),
+            (
+                b"a\x80b",
+                AnsiCodePage::Windows1252,
+                "a\u{20AC}b",
+                &[0, 1, 2],
+            ),
+            (
+                b"a\x82\xd9b",
+                AnsiCodePage::ShiftJis,
+                "a\u{307B}b",
+                &[0, 1, 3],
+            ),
+        ];
+
+        for (buffer, code_page, text, offsets) in cases {
+            let options = ParserOptions::new().ansi_code_page(code_page);
+            let decoded = DecodedText::new(buffer, &options);
+
+            assert_eq!(decoded.text(), text);
+            assert_eq!(
+                Ok(decoded.text().to_owned()),
+                crate::decode_data(buffer, &options)
+            );
+            assert_eq!(decoded.source_len_bytes(), buffer.len());
+
+            for (i, &offset) in offsets.iter().enumerate() {
+                assert_eq!(decoded.byte_offset(i), Some(offset), "{text:?}[{i}]");
+            }
+
+            assert_eq!(decoded.byte_offset(offsets.len()), Some(buffer.len()));
+            assert_eq!(decoded.byte_offset(offsets.len() + 1), None);
+        }
+    }
+
+    #[test]
+    fn embedded_bom_warning() {
+        let options = ParserOptions::new().ansi_code_page(AnsiCodePage::Windows1252);
+        let buffer = b"[Strings]\nName = \"a\xFF\xFEb\"";
+        let decoded = DecodedText::new(buffer, &options);
+
+        assert_eq!(decoded.text(), "[Strings]\nName = \"a\u{FF}\u{FE}b\"");
+        assert_eq!(
+            decoded.warnings(),
+            &[DecodeWarning::EmbeddedBom { offset: 19 }]
+        );
+
+        let utf16 = [BOM_LE.as_slice(), b"a\x00"].concat();
+        assert_eq!(DecodedText::new(&utf16, &options).warnings(), &[]);
+        assert_eq!(DecodedText::new(b"[Strings]", &options).warnings(), &[]);
+    }
+
+    #[test]
+    fn invalid_utf16_options() {
+        let buffer = [BOM_LE.as_slice(), b"a\x00\x00\xDCb\x00"].concat();
+
+        let options = ParserOptions::new().replacement_char(None);
+        let decoded = DecodedText::new(&buffer, &options);
+        assert_eq!(decoded.text(), "ab");
+        assert_eq!(
+            Ok(decoded.text().to_owned()),
+            crate::decode_data(&buffer, &options)
+        );
+        assert_eq!(decoded.byte_offset(1), Some(6));
+        assert_eq!(
+            decoded.warnings(),
+            &[DecodeWarning::InvalidUtf16 { offset: 4 }]
+        );
+
+        let options = ParserOptions::new().strict_utf16(true);
+        assert_eq!(DecodedText::new(&buffer, &options).text(), "a\u{FFFD}b");
+        assert_eq!(
+            crate::decode_data(&buffer, &options),
+            Err(crate::ParseError::InvalidUtf16 { offset: 4 })
+        );
+    }
+}
@@ -0,0 +1,260 @@
+use crate::Inf;
+use crate::section::{Entry, Section, Value};
+
+/// The changes between two versions of an INF file, as returned by [`Inf::diff`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InfDiff {
+    /// The names of sections that only exist in the new file, in the order they appear there.
+    pub added_sections: Vec<String>,
+    /// The names of sections that only exist in the old file, in the order they appear there.
+    pub removed_sections: Vec<String>,
+    /// The sections that exist in both files but whose entries differ, in the order they
+    /// appear in the old file.
+    pub changed_sections: Vec<SectionDiff>,
+}
+
+impl InfDiff {
+    /// Returns `true` if both files have the same sections and entries.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.added_sections.is_empty()
+            && self.removed_sections.is_empty()
+            && self.changed_sections.is_empty()
+    }
+}
+
+/// The changes to the entries of a section that exists in both files.
+///
+/// Items are matched by key, ignoring ASCII case; when a key appears more than once, each
+/// occurrence is matched with the occurrence at the same position in the other file. Value-only
+/// entries have nothing to match them by, so they are only ever added or removed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SectionDiff {
+    /// The name of the section, as written in the old file.
+    pub name: String,
+    /// Entries that only exist in the new file, in the order they appear there.
+    pub added: Vec<Entry>,
+    /// Entries that only exist in the old file, in the order they appear there.
+    pub removed: Vec<Entry>,
+    /// Items whose value changed, in the order they appear in the old file.
+    pub changed: Vec<EntryChange>,
+}
+
+/// An item that exists in both files with a different value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntryChange {
+    /// The key of the item, as written in the old file.
+    pub key: String,
+    /// The value in the old file.
+    pub old: Value,
+    /// The value in the new file.
+    pub new: Value,
+}
+
+impl Inf {
+    /// Compares this file with a newer version of it, `other`.
+    ///
+    /// Sections are matched by name, ignoring ASCII case. See [`SectionDiff`] for how the
+    /// entries of a section are matched.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use inf::Inf;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let old = Inf::from_bytes(b"[Version]\nDriverVer = 1.0\n[Old]")?;
+    /// let new = Inf::from_bytes(b"[version]\nDriverVer = 2.0\n[New]")?;
+    /// let diff = old.diff(&new);
+    ///
+    /// assert_eq!(diff.added_sections, ["New"]);
+    /// assert_eq!(diff.removed_sections, ["Old"]);
+    /// assert_eq!(diff.changed_sections[0].changed[0].key, "DriverVer");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn diff(&self, other: &Inf) -> InfDiff {
+        let mut diff = InfDiff::default();
+
+        for section in self.sections() {
+            match other.get(section.name()) {
+                Some(new) => {
+                    let section_diff = diff_entries(section, new);
+
+                    if !section_diff.added.is_empty()
+                        || !section_diff.removed.is_empty()
+                        || !section_diff.changed.is_empty()
+                    {
+                        diff.changed_sections.push(section_diff);
+                    }
+                }
+                None => diff.removed_sections.push(section.name().to_owned()),
+            }
+        }
+
+        diff.added_sections = other
+            .sections()
+            .iter()
+            .filter(|section| !self.has_section(section.name()))
+            .map(|section| section.name().to_owned())
+            .collect();
+
+        diff
+    }
+}
+
+fn diff_entries(old: &Section, new: &Section) -> SectionDiff {
+    let mut diff = SectionDiff {
+        name: old.name().to_owned(),
+        added: Vec::new(),
+        removed: Vec::new(),
+        changed: Vec::new(),
+    };
+    let new_matched = match_entries(new.entries(), old.entries());
+
+    for (entry, matched) in old
+        .entries()
+        .iter()
+        .zip(match_entries(old.entries(), new.entries()))
+    {
+        match (entry, matched) {
+            (Entry::Item(key, old), Some(Entry::Item(_, new))) if old != new => {
+                diff.changed.push(EntryChange {
+                    key: key.clone(),
+                    old: old.clone(),
+                    new: new.clone(),
+                });
+            }
+            (_, Some(_)) => {}
+            (entry, None) => diff.removed.push(entry.clone()),
+        }
+    }
+
+    for (entry, matched) in new.entries().iter().zip(new_matched) {
+        if matched.is_none() {
+            diff.added.push(entry.clone());
+        }
+    }
+
+    diff
+}
+
+/// Returns the entry of `other` that each entry of `entries` is matched with, if any.
+///
+/// The n-th item with a given key is matched with the n-th item with the same key in `other`,
+/// while a value-only entry is matched with the first equal value-only entry that has not been
+/// matched already.
+fn match_entries<'a>(entries: &[Entry], other: &'a [Entry]) -> Vec<Option<&'a Entry>> {
+    let mut used = vec![false; other.len()];
+
+    entries
+        .iter()
+        .enumerate()
+        .map(|(i, entry)| {
+            let j = match entry {
+                Entry::Item(key, _) => {
+                    let occurrence = entries[..i]
+                        .iter()
+                        .filter(|e| e.key().is_some_and(|k| k.eq_ignore_ascii_case(key)))
+                        .count();
+
+                    other
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, e)| e.key().is_some_and(|k| k.eq_ignore_ascii_case(key)))
+                        .nth(occurrence)
+                        .map(|(j, _)| j)
+                }
+                Entry::Value(_) => (0..other.len()).find(|&j| !used[j] && other[j] == *entry),
+            }?;
+
+            used[j] = true;
+            Some(&other[j])
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diff() {
+        let old = Inf::from_bytes(
+            b"\
+            [Version]\n\
+            Signature = \"$Windows NT$\"\n\
+            DriverVer = 01/01/2020,1.0.0.0\n\
+            [Files]\n\
+            a.sys\n\
+            b.sys\n\
+            [Install]\n\
+            CopyFiles = Files\n\
+            AddReg = A\n\
+            AddReg = B\n\
+            [Removed]\
+        ",
+        )
+        .expect("failed to parse hardcoded INF file");
+        let new = Inf::from_bytes(
+            b"\
+            [version]\n\
+            driverver = 02/02/2022,2.0.0.0\n\
+            Signature = \"$Windows NT$\"\n\
+            [Added]\n\
+            [Files]\n\
+            b.sys\n\
+            c.sys\n\
+            [Install]\n\
+            CopyFiles = Files\n\
+            AddReg = A\n\
+            AddReg = C\n\
+            Needs = Base\
+        ",
+        )
+        .expect("failed to parse hardcoded INF file");
+        let diff = old.diff(&new);
+
+        assert_eq!(
+            diff,
+            InfDiff {
+                added_sections: vec!["Added".to_owned()],
+                removed_sections: vec!["Removed".to_owned()],
+                changed_sections: vec![
+                    SectionDiff {
+                        name: "Version".to_owned(),
+                        added: vec![],
+                        removed: vec![],
+                        changed: vec![EntryChange {
+                            key: "DriverVer".to_owned(),
+                            old: Value::list_from(&["01/01/2020", "1.0.0.0"]),
+                            new: Value::list_from(&["02/02/2022", "2.0.0.0"]),
+                        }],
+                    },
+                    SectionDiff {
+                        name: "Files".to_owned(),
+                        added: vec![Entry::Value(Value::Raw("c.sys".to_owned()))],
+                        removed: vec![Entry::Value(Value::Raw("a.sys".to_owned()))],
+                        changed: vec![],
+                    },
+                    SectionDiff {
+                        name: "Install".to_owned(),
+                        added: vec![Entry::Item(
+                            "Needs".to_owned(),
+                            Value::Raw("Base".to_owned())
+                        )],
+                        removed: vec![],
+                        changed: vec![EntryChange {
+                            key: "AddReg".to_owned(),
+                            old: Value::Raw("B".to_owned()),
+                            new: Value::Raw("C".to_owned()),
+                        }],
+                    },
+                ],
+            }
+        );
+        assert!(!diff.is_empty());
+        assert!(old.diff(&old).is_empty());
+    }
+}
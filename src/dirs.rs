@@ -0,0 +1,138 @@
+use std::collections::HashMap;
+
+use crate::Inf;
+use crate::section::{Entry, Section};
+
+impl Inf {
+    /// Returns the destination directories listed in the `[DestinationDirs]` section.
+    ///
+    /// Each entry has the form `file-list-section = dirid[,subdir]` and is keyed by the name of
+    /// the file-list section as written. The `DefaultDestDir` entry, which applies to every
+    /// file-list section without an entry of its own, is returned under its own key. Entries
+    /// without a numeric dirid are skipped, and only the first of several entries with the same
+    /// key (ignoring ASCII case) is kept.
+    ///
+    /// Section names are not case-sensitive, so use [`Inf::destination_dir`] to look up the
+    /// directory of a file-list section by a name that may be written differently.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use inf::Inf;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let inf = Inf::from_bytes(b"[DestinationDirs]\nDefaultDestDir = 12\nFiles = 10,Fonts")?;
+    /// let dirs = inf.destination_dirs();
+    /// assert_eq!(dirs["DefaultDestDir"], (12, None));
+    /// assert_eq!(dirs["Files"], (10, Some("Fonts".to_owned())));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn destination_dirs(&self) -> HashMap<String, (u32, Option<String>)> {
+        let mut dirs = HashMap::<String, (u32, Option<String>)>::new();
+
+        for (key, dir) in self.destination_dir_entries() {
+            if !dirs.keys().any(|k| k.eq_ignore_ascii_case(&key)) {
+                dirs.insert(key, dir);
+            }
+        }
+
+        dirs
+    }
+
+    /// Returns the destination directory of the file-list section named `section` (ignoring
+    /// ASCII case), falling back to the `DefaultDestDir` entry if it has none of its own.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use inf::Inf;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let inf = Inf::from_bytes(b"[DestinationDirs]\nDefaultDestDir = 12\nFiles = 10,Fonts")?;
+    /// assert_eq!(inf.destination_dir("FILES"), Some((10, Some("Fonts".to_owned()))));
+    /// assert_eq!(inf.destination_dir("Other"), Some((12, None)));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn destination_dir(&self, section: &str) -> Option<(u32, Option<String>)> {
+        self.destination_dir_entries()
+            .find(|(key, _)| key.eq_ignore_ascii_case(section))
+            .or_else(|| {
+                self.destination_dir_entries()
+                    .find(|(key, _)| key.eq_ignore_ascii_case("DefaultDestDir"))
+            })
+            .map(|(_, dir)| dir)
+    }
+
+    fn destination_dir_entries(&self) -> impl Iterator<Item = (String, (u32, Option<String>))> {
+        self.get("DestinationDirs")
+            .map(Section::entries)
+            .unwrap_or_default()
+            .iter()
+            .filter_map(parse_destination_dir)
+    }
+}
+
+fn parse_destination_dir(entry: &Entry) -> Option<(String, (u32, Option<String>))> {
+    let key = entry.key()?;
+    let fields = entry.value().as_slice();
+    let dirid = fields.first()?.parse::<u32>().ok()?;
+    let subdir = fields.get(1).filter(|subdir| !subdir.is_empty()).cloned();
+
+    Some((key.to_owned(), (dirid, subdir)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn destination_dirs() {
+        let buffer = b"\
+            [DestinationDirs]\n\
+            DefaultDestDir = 12\n\
+            Drivers.Copy = 12,UMDF\n\
+            Fonts.Copy = 20,\n\
+            Bad.Copy = %DirId%\n\
+            13\
+        ";
+        let inf = Inf::from_bytes(buffer).expect("failed to parse hardcoded INF file");
+
+        assert_eq!(
+            inf.destination_dirs(),
+            HashMap::from([
+                ("DefaultDestDir".to_owned(), (12, None)),
+                ("Drivers.Copy".to_owned(), (12, Some("UMDF".to_owned()))),
+                ("Fonts.Copy".to_owned(), (20, None)),
+            ])
+        );
+    }
+
+    #[test]
+    fn destination_dirs_ignore_case() {
+        let buffer = b"\
+            [destinationdirs]\n\
+            defaultdestdir = 12\n\
+            Drivers.Copy = 12,UMDF\n\
+            DRIVERS.COPY = 11\
+        ";
+        let inf = Inf::from_bytes(buffer).expect("failed to parse hardcoded INF file");
+
+        assert_eq!(
+            inf.destination_dirs(),
+            HashMap::from([
+                ("defaultdestdir".to_owned(), (12, None)),
+                ("Drivers.Copy".to_owned(), (12, Some("UMDF".to_owned()))),
+            ])
+        );
+        assert_eq!(
+            inf.destination_dir("drivers.copy"),
+            Some((12, Some("UMDF".to_owned())))
+        );
+        assert_eq!(inf.destination_dir("Fonts.Copy"), Some((12, None)));
+        assert_eq!(Inf::default().destination_dir("Fonts.Copy"), None);
+    }
+}
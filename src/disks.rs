@@ -0,0 +1,169 @@
+use crate::Inf;
+use crate::error::MalformedEntryError;
+use crate::section::{Entry, Section};
+
+/// A source disk described by an entry of the `[SourceDisksNames]` section.
+///
+/// Each entry has the form `diskid = disk-description[,[tag-or-cab-file],[unused],[path]]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiskInfo {
+    /// A description of the disk, often a `%strkey%` token that has not been expanded.
+    pub description: String,
+    /// A file used to verify that the correct disk is present, or a cabinet file.
+    pub tag_file: Option<String>,
+    /// The directory on the disk containing the source files, relative to its root.
+    pub path: Option<String>,
+}
+
+impl Inf {
+    /// Returns the disks listed in the `[SourceDisksNames]` section, keyed by disk id.
+    ///
+    /// Disks listed in a decorated section, such as `[SourceDisksNames.amd64]`, follow those of
+    /// the undecorated section, so a disk id may appear once for each platform. Entries that
+    /// are not keyed by a numeric disk id, or that have no description, are skipped. Use
+    /// [`Inf::try_source_disk_names`] to reject them instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use inf::Inf;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let inf = Inf::from_bytes(b"[SourceDisksNames]\n1 = %DiskName%,,,\\x86")?;
+    /// let disks = inf.source_disk_names();
+    /// assert_eq!(disks[0].0, 1);
+    /// assert_eq!(disks[0].1.path.as_deref(), Some("\\x86"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn source_disk_names(&self) -> Vec<(u32, DiskInfo)> {
+        self.source_disk_sections()
+            .into_iter()
+            .flat_map(Section::entries)
+            .filter_map(parse_disk)
+            .collect()
+    }
+
+    /// Returns the disks listed in the `[SourceDisksNames]` section and its decorated
+    /// variants, keyed by disk id (see [`Inf::source_disk_names`]).
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`MalformedEntryError`] for the first entry that is not keyed by a numeric
+    /// disk id, or that has no description.
+    pub fn try_source_disk_names(&self) -> Result<Vec<(u32, DiskInfo)>, MalformedEntryError> {
+        self.source_disk_sections()
+            .into_iter()
+            .flat_map(|section| section.entries().iter().map(move |entry| (section, entry)))
+            .map(|(section, entry)| {
+                parse_disk(entry).ok_or_else(|| MalformedEntryError::new(section.name(), entry))
+            })
+            .collect()
+    }
+
+    /// Returns `[SourceDisksNames]` followed by its decorated variants in file order.
+    fn source_disk_sections(&self) -> Vec<&Section> {
+        let mut sections = self.sections_with_base("SourceDisksNames");
+        sections.sort_by_key(|section| section.decoration().is_some());
+        sections
+    }
+}
+
+fn parse_disk(entry: &Entry) -> Option<(u32, DiskInfo)> {
+    let id = entry.key()?.parse::<u32>().ok()?;
+    let fields = entry.value().as_slice();
+    let field = |i: usize| fields.get(i).filter(|field| !field.is_empty()).cloned();
+
+    let disk = DiskInfo {
+        description: field(0)?,
+        tag_file: field(1),
+        path: field(3),
+    };
+
+    Some((id, disk))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn source_disk_names() {
+        let buffer = b"\
+            [SourceDisksNames]\n\
+            1 = %DiskName%,contoso.tag,,\\x86\n\
+            2 = \"Second Disk\"\n\
+            three = \"Not a disk id\"\n\
+            4 = ,missing.tag\
+        ";
+        let inf = Inf::from_bytes(buffer).expect("failed to parse hardcoded INF file");
+
+        assert_eq!(
+            inf.source_disk_names(),
+            vec![
+                (
+                    1,
+                    DiskInfo {
+                        description: "%DiskName%".to_owned(),
+                        tag_file: Some("contoso.tag".to_owned()),
+                        path: Some("\\x86".to_owned()),
+                    }
+                ),
+                (
+                    2,
+                    DiskInfo {
+                        description: "Second Disk".to_owned(),
+                        tag_file: None,
+                        path: None,
+                    }
+                ),
+            ]
+        );
+
+        let err = inf
+            .try_source_disk_names()
+            .expect_err("expected a malformed entry");
+        assert_eq!(err.entry().key(), Some("three"));
+        assert_eq!(
+            err.to_string(),
+            "malformed entry in section [SourceDisksNames]"
+        );
+    }
+
+    #[test]
+    fn source_disk_names_decorated() {
+        let buffer = b"\
+            [SourceDisksNames.amd64]\n\
+            1 = %DiskName%,,,\\amd64\n\
+            [SourceDisksNames]\n\
+            1 = %DiskName%\n\
+            [sourcedisksnames.x86]\n\
+            1 = %DiskName%,,,\\x86\n\
+            bad = \"Not a disk id\"\
+        ";
+        let inf = Inf::from_bytes(buffer).expect("failed to parse hardcoded INF file");
+        let disk = |path: Option<&str>| DiskInfo {
+            description: "%DiskName%".to_owned(),
+            tag_file: None,
+            path: path.map(str::to_owned),
+        };
+
+        assert_eq!(
+            inf.source_disk_names(),
+            vec![
+                (1, disk(None)),
+                (1, disk(Some("\\amd64"))),
+                (1, disk(Some("\\x86"))),
+            ]
+        );
+
+        let err = inf
+            .try_source_disk_names()
+            .expect_err("expected a malformed entry");
+        assert_eq!(
+            err.to_string(),
+            "malformed entry in section [sourcedisksnames.x86]"
+        );
+    }
+}
@@ -1,22 +1,92 @@
 use std::{error, fmt, io};
 
+use crate::options::ParseLimit;
+use crate::section::{Entry, MAX_NAME_LEN};
+
 #[derive(Debug)]
 pub enum ParseError {
     ReadFailure { source: io::Error },
+    InvalidUtf16 { offset: usize },
     SectionNameEmpty,
     SectionNameTooLong,
-    UnexpectedCharacter { c: char },
-    UnterminatedString,
+    SectionNameInvalid { name: String },
+    EntryBeforeSection,
+    LimitExceeded { limit: ParseLimit },
+    UnexpectedCharacter { c: char, section: Option<String> },
+    UnterminatedString { section: Option<String> },
+    EmptyKey { section: Option<String> },
+}
+
+/// Errors are equal if they are the same variant with equal fields. I/O failures are compared
+/// by their [`io::ErrorKind`], since [`io::Error`] itself cannot be compared.
+impl PartialEq for ParseError {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::ReadFailure { source: a }, Self::ReadFailure { source: b }) => {
+                a.kind() == b.kind()
+            }
+            (Self::SectionNameEmpty, Self::SectionNameEmpty)
+            | (Self::SectionNameTooLong, Self::SectionNameTooLong)
+            | (Self::EntryBeforeSection, Self::EntryBeforeSection) => true,
+            (Self::InvalidUtf16 { offset: a }, Self::InvalidUtf16 { offset: b }) => a == b,
+            (Self::SectionNameInvalid { name: a }, Self::SectionNameInvalid { name: b }) => a == b,
+            (Self::LimitExceeded { limit: a }, Self::LimitExceeded { limit: b }) => a == b,
+            (
+                Self::UnexpectedCharacter {
+                    c: a,
+                    section: a_section,
+                },
+                Self::UnexpectedCharacter {
+                    c: b,
+                    section: b_section,
+                },
+            ) => a == b && a_section == b_section,
+            (Self::UnterminatedString { section: a }, Self::UnterminatedString { section: b })
+            | (Self::EmptyKey { section: a }, Self::EmptyKey { section: b }) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for ParseError {}
+
+impl ParseError {
+    /// Attaches the name of the section being parsed, unless one is already present.
+    pub(crate) fn in_section(mut self, name: &str) -> Self {
+        match self {
+            Self::UnexpectedCharacter {
+                ref mut section, ..
+            }
+            | Self::UnterminatedString { ref mut section }
+            | Self::EmptyKey { ref mut section } => {
+                section.get_or_insert_with(|| name.to_owned());
+            }
+            Self::ReadFailure { .. }
+            | Self::InvalidUtf16 { .. }
+            | Self::SectionNameEmpty
+            | Self::SectionNameTooLong
+            | Self::SectionNameInvalid { .. }
+            | Self::EntryBeforeSection
+            | Self::LimitExceeded { .. } => {}
+        }
+
+        self
+    }
 }
 
 impl error::Error for ParseError {
     fn source(&self) -> Option<&(dyn error::Error + 'static)> {
         match *self {
             Self::ReadFailure { ref source } => Some(source),
-            Self::SectionNameEmpty
+            Self::InvalidUtf16 { .. }
+            | Self::SectionNameEmpty
             | Self::SectionNameTooLong
+            | Self::SectionNameInvalid { .. }
+            | Self::EntryBeforeSection
+            | Self::LimitExceeded { .. }
             | Self::UnexpectedCharacter { .. }
-            | Self::UnterminatedString => None,
+            | Self::UnterminatedString { .. }
+            | Self::EmptyKey { .. } => None,
         }
     }
 }
@@ -25,10 +95,188 @@ impl fmt::Display for ParseError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match *self {
             Self::ReadFailure { source: _ } => "failed to read data".fmt(f),
+            Self::InvalidUtf16 { offset } => write!(f, "invalid UTF-16 at byte offset {offset}"),
+            Self::SectionNameEmpty => "section name cannot be empty".fmt(f),
+            Self::SectionNameTooLong => "section name cannot exceed 255 characters".fmt(f),
+            Self::SectionNameInvalid { ref name } => {
+                write!(f, "section name cannot contain ']': [{name}]")
+            }
+            Self::EntryBeforeSection => "entry found before the first section header".fmt(f),
+            Self::LimitExceeded { limit } => match limit {
+                ParseLimit::Sections => "too many sections".fmt(f),
+                ParseLimit::EntriesPerSection => "too many entries in a section".fmt(f),
+                ParseLimit::ValueLen => "entry is too long".fmt(f),
+                ParseLimit::ContinuedLines => "entry is continued over too many lines".fmt(f),
+            },
+            Self::UnexpectedCharacter { c, ref section } => {
+                write!(f, "unexpected character: {c:?}")?;
+                fmt_section(f, section.as_deref())
+            }
+            Self::UnterminatedString { ref section } => {
+                "unterminated string".fmt(f)?;
+                fmt_section(f, section.as_deref())
+            }
+            Self::EmptyKey { ref section } => {
+                "key cannot be empty".fmt(f)?;
+                fmt_section(f, section.as_deref())
+            }
+        }
+    }
+}
+
+fn fmt_section(f: &mut fmt::Formatter<'_>, section: Option<&str>) -> fmt::Result {
+    match section {
+        Some(name) => write!(f, " in section [{name}]"),
+        None => Ok(()),
+    }
+}
+
+/// A [`ParseError`] along with the text around the point where parsing stopped, as returned
+/// by [`Parser::parse_with_context`](crate::Parser::parse_with_context).
+#[derive(Debug, PartialEq, Eq)]
+pub struct ContextualParseError {
+    error: ParseError,
+    offset: usize,
+    snippet: String,
+}
+
+impl ContextualParseError {
+    /// The number of characters kept on either side of the offset by [`Self::snippet`].
+    pub const CONTEXT_CHARS: usize = 40;
+
+    pub(crate) fn new(error: ParseError, text: &str, offset: usize) -> Self {
+        let start = text[..offset]
+            .char_indices()
+            .rev()
+            .nth(Self::CONTEXT_CHARS - 1)
+            .map_or(0, |(i, _)| i);
+        let end = text[offset..]
+            .char_indices()
+            .nth(Self::CONTEXT_CHARS)
+            .map_or(text.len(), |(i, _)| offset + i);
+
+        Self {
+            error,
+            offset,
+            snippet: text[start..end].to_owned(),
+        }
+    }
+
+    #[must_use]
+    pub fn error(&self) -> &ParseError {
+        &self.error
+    }
+
+    #[must_use]
+    pub fn into_error(self) -> ParseError {
+        self.error
+    }
+
+    /// Returns the byte offset into the text at which parsing stopped.
+    #[must_use]
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns up to [`Self::CONTEXT_CHARS`] characters of text on either side of
+    /// [`Self::offset`], exactly as written in the source.
+    #[must_use]
+    pub fn snippet(&self) -> &str {
+        &self.snippet
+    }
+}
+
+impl From<ContextualParseError> for ParseError {
+    fn from(err: ContextualParseError) -> Self {
+        err.error
+    }
+}
+
+impl error::Error for ContextualParseError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+impl fmt::Display for ContextualParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} near {:?}", self.error, self.snippet)
+    }
+}
+
+/// An error returned when an edit to an [`Inf`](crate::Inf) cannot be applied.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EditError {
+    SectionNotFound { name: String },
+    SectionNameEmpty,
+    SectionNameTooLong,
+    SectionNameInvalid { name: String },
+}
+
+impl EditError {
+    /// Checks `name` against the rules the parser enforces for a section header, so that an
+    /// edited file can always be written out and parsed back.
+    pub(crate) fn check_section_name(name: &str) -> Result<(), Self> {
+        if name.is_empty() {
+            Err(Self::SectionNameEmpty)
+        } else if name.len() > MAX_NAME_LEN {
+            Err(Self::SectionNameTooLong)
+        } else if name.contains(']') {
+            Err(Self::SectionNameInvalid {
+                name: name.to_owned(),
+            })
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl error::Error for EditError {}
+
+impl fmt::Display for EditError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::SectionNotFound { ref name } => write!(f, "section not found: [{name}]"),
             Self::SectionNameEmpty => "section name cannot be empty".fmt(f),
             Self::SectionNameTooLong => "section name cannot exceed 255 characters".fmt(f),
-            Self::UnexpectedCharacter { c } => write!(f, "unexpected character: {c:?}"),
-            Self::UnterminatedString => "unterminated string".fmt(f),
+            Self::SectionNameInvalid { ref name } => {
+                write!(f, "section name cannot contain ']': [{name}]")
+            }
+        }
+    }
+}
+
+/// An entry that does not follow the format expected for the section it appears in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MalformedEntryError {
+    section: String,
+    entry: Entry,
+}
+
+impl MalformedEntryError {
+    pub(crate) fn new(section: &str, entry: &Entry) -> Self {
+        Self {
+            section: section.to_owned(),
+            entry: entry.clone(),
         }
     }
+
+    /// Returns the name of the section containing the malformed entry.
+    #[must_use]
+    pub fn section(&self) -> &str {
+        &self.section
+    }
+
+    #[must_use]
+    pub fn entry(&self) -> &Entry {
+        &self.entry
+    }
+}
+
+impl error::Error for MalformedEntryError {}
+
+impl fmt::Display for MalformedEntryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "malformed entry in section [{}]", self.section)
+    }
 }
@@ -0,0 +1,174 @@
+use crate::Inf;
+use crate::orphans::is_named_or_decorated;
+
+/// Directives whose values name either a file list section or, when prefixed with `@`, a
+/// single file.
+const FILE_DIRECTIVES: &[&str] = &["CopyFiles", "DelFiles", "RenFiles"];
+
+/// A single field of a `CopyFiles`, `DelFiles` or `RenFiles` directive.
+///
+/// # Examples
+///
+/// ```
+/// use inf::FileReference;
+///
+/// assert_eq!(FileReference::parse("@driver.sys"), FileReference::File("driver.sys"));
+/// assert_eq!(FileReference::parse("Driver_Files"), FileReference::Section("Driver_Files"));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileReference<'a> {
+    /// A single file, written as `@file`; the name excludes the `@`.
+    File(&'a str),
+    /// The name of a file list section whose entries name the files.
+    Section(&'a str),
+}
+
+impl<'a> FileReference<'a> {
+    /// Parses a field of a file directive. Whitespace between `@` and the file name is
+    /// ignored.
+    #[must_use]
+    pub fn parse(value: &'a str) -> Self {
+        match value.strip_prefix('@') {
+            Some(file) => Self::File(file.trim_ascii()),
+            None => Self::Section(value),
+        }
+    }
+}
+
+impl Inf {
+    /// Returns the name of every file referenced by the `CopyFiles`, `DelFiles` and `RenFiles`
+    /// directives, as well as those listed by `[SourceDisksFiles]` sections.
+    ///
+    /// Each field of a directive is read as a [`FileReference`]: `@file` names a single file,
+    /// while any other value names a file list section whose entries are read instead. Both
+    /// the destination and source file names of an entry are included (e.g. `new.sys,old.sys`
+    /// in a `RenFiles` section). File list sections that do not exist in this file are
+    /// skipped.
+    ///
+    /// Each file is returned once, in the order it is first found, comparing names ignoring
+    /// ASCII case.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use inf::Inf;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let buffer = b"\
+    ///     [DefaultInstall]\n\
+    ///     CopyFiles = @driver.inf, Driver_Files\n\
+    ///     [Driver_Files]\n\
+    ///     driver.sys\n\
+    ///     [SourceDisksFiles]\n\
+    ///     driver.sys = 1\
+    /// ";
+    /// let inf = Inf::from_bytes(buffer)?;
+    /// assert_eq!(inf.referenced_files(), ["driver.inf", "driver.sys"]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn referenced_files(&self) -> Vec<String> {
+        let mut files = Vec::<String>::new();
+        let mut add = |file: &str| {
+            if !file.is_empty() && !files.iter().any(|f| f.eq_ignore_ascii_case(file)) {
+                files.push(file.to_owned());
+            }
+        };
+
+        for section in self.sections() {
+            if is_named_or_decorated(section.name(), "SourceDisksFiles") {
+                section.items().for_each(|(file, _)| add(file));
+                continue;
+            }
+
+            let values = section
+                .items()
+                .filter(|(key, _)| FILE_DIRECTIVES.iter().any(|d| d.eq_ignore_ascii_case(key)))
+                .flat_map(|(_, value)| value.iter());
+
+            for value in values {
+                match FileReference::parse(value) {
+                    FileReference::File(file) => add(file),
+                    FileReference::Section(name) => {
+                        if let Some(file_list) = self.get(name) {
+                            file_list
+                                .entries()
+                                .iter()
+                                .flat_map(|entry| entry.value().iter().take(2))
+                                .for_each(&mut add);
+                        }
+                    }
+                }
+            }
+        }
+
+        files
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn referenced_files() {
+        let buffer = b"\
+            [DefaultInstall.NTamd64]\n\
+            CopyFiles = Driver_Files, @Driver.inf, Missing_Files\n\
+            DelFiles = Old_Files\n\
+            RenFiles = Renamed_Files\n\
+            AddReg = Not_Files\n\
+            [Driver_Files]\n\
+            driver.sys\n\
+            helper.dll,helper_x64.dll,,0x4\n\
+            [Old_Files]\n\
+            legacy.sys,,,0x1\n\
+            DRIVER.SYS\n\
+            [Renamed_Files]\n\
+            new.sys,old.sys\n\
+            [Not_Files]\n\
+            HKR,,Value,,not_a_file.sys\n\
+            [SourceDisksFiles.amd64]\n\
+            driver.sys = 1\n\
+            payload.bin = 1,,1024\
+        ";
+        let inf = Inf::from_bytes(buffer).expect("failed to parse hardcoded INF file");
+
+        assert_eq!(
+            inf.referenced_files(),
+            vec![
+                "driver.sys",
+                "helper.dll",
+                "helper_x64.dll",
+                "Driver.inf",
+                "legacy.sys",
+                "new.sys",
+                "old.sys",
+                "payload.bin",
+            ]
+        );
+    }
+
+    #[test]
+    fn file_reference() {
+        let inf = Inf::from_bytes(b"[Install]\nCopyFiles = @a.sys, Files, @ b.sys,More_Files")
+            .expect("failed to parse hardcoded INF file");
+        let references = inf
+            .get_list("Install", "CopyFiles")
+            .expect("hardcoded INF file has CopyFiles")
+            .iter()
+            .map(|value| FileReference::parse(value))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            references,
+            vec![
+                FileReference::File("a.sys"),
+                FileReference::Section("Files"),
+                FileReference::File("b.sys"),
+                FileReference::Section("More_Files"),
+            ]
+        );
+    }
+}
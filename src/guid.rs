@@ -0,0 +1,102 @@
+use std::fmt;
+
+/// A GUID, such as the class GUID of a device (`ClassGuid = {4d36e97d-...}`).
+///
+/// The fields follow the layout of the Windows `GUID` structure. See [`Value::as_guid`] for
+/// the form it is parsed from; the same form is used when formatting it.
+///
+/// [`Value::as_guid`]: crate::Value::as_guid
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Guid {
+    pub data1: u32,
+    pub data2: u16,
+    pub data3: u16,
+    pub data4: [u8; 8],
+}
+
+impl Guid {
+    /// Parses the braced, hyphenated form of a GUID, ignoring the case of hex digits and any
+    /// surrounding ASCII whitespace.
+    pub(crate) fn parse(value: &str) -> Option<Self> {
+        let value = value.trim_ascii().strip_prefix('{')?.strip_suffix('}')?;
+        let groups = value.split('-').collect::<Vec<_>>();
+        let [data1, data2, data3, data4_head, data4_tail] = groups.as_slice() else {
+            return None;
+        };
+
+        let lengths = [8, 4, 4, 4, 12];
+        let valid = groups
+            .iter()
+            .zip(lengths)
+            .all(|(group, len)| group.len() == len && group.bytes().all(|b| b.is_ascii_hexdigit()));
+
+        if !valid {
+            return None;
+        }
+
+        let data4 = u64::from_str_radix(&format!("{data4_head}{data4_tail}"), 16).ok()?;
+
+        Some(Self {
+            data1: u32::from_str_radix(data1, 16).ok()?,
+            data2: u16::from_str_radix(data2, 16).ok()?,
+            data3: u16::from_str_radix(data3, 16).ok()?,
+            data4: data4.to_be_bytes(),
+        })
+    }
+}
+
+/// Formats the GUID in its braced, hyphenated form with lowercase hex digits.
+impl fmt::Display for Guid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (head, tail) = self.data4.split_at(2);
+
+        write!(
+            f,
+            "{{{:08x}-{:04x}-{:04x}-",
+            self.data1, self.data2, self.data3
+        )?;
+        head.iter().try_for_each(|byte| write!(f, "{byte:02x}"))?;
+        "-".fmt(f)?;
+        tail.iter().try_for_each(|byte| write!(f, "{byte:02x}"))?;
+        "}".fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_and_display() {
+        let guid = Guid::parse(" {4D36E97D-e325-11CE-bfc1-08002BE10318} ").unwrap();
+
+        assert_eq!(
+            guid,
+            Guid {
+                data1: 0x4d36_e97d,
+                data2: 0xe325,
+                data3: 0x11ce,
+                data4: [0xbf, 0xc1, 0x08, 0x00, 0x2b, 0xe1, 0x03, 0x18],
+            }
+        );
+        assert_eq!(guid.to_string(), "{4d36e97d-e325-11ce-bfc1-08002be10318}");
+    }
+
+    #[test]
+    fn parse_malformed() {
+        for value in [
+            "4d36e97d-e325-11ce-bfc1-08002be10318",
+            "{4d36e97d-e325-11ce-bfc1-08002be10318",
+            "{4d36e97de32511cebfc108002be10318}",
+            "{4d36e97d-e325-11ce-bfc108002be10318}",
+            "{4d36e97d-e325-11ce-bfc1-08002be1031}",
+            "{4d36e97d-e325-11ce-bfc1-08002be103180}",
+            "{4d36e97g-e325-11ce-bfc1-08002be10318}",
+            "{+d36e97d-e325-11ce-bfc1-08002be10318}",
+            "{4d36e97d-e325-11ce-bfc1-08002be10318-}",
+            "",
+        ] {
+            assert_eq!(Guid::parse(value), None, "{value}");
+        }
+    }
+}
@@ -4,21 +4,58 @@
     clippy::complexity,
     clippy::perf,
     clippy::style,
-    clippy::pedantic
+    clippy::pedantic,
+    // A library should never write to the standard streams of the program using it.
+    clippy::print_stdout,
+    clippy::print_stderr,
+    clippy::dbg_macro
 )]
 
+mod addreg;
+mod borrowed;
+mod decoded;
+mod diff;
+mod dirs;
+mod disks;
 mod error;
+mod files;
+mod guid;
+mod macros;
+mod manufacturers;
+mod needs;
+mod options;
+mod orphans;
 mod parser;
 mod section;
+mod stats;
 pub mod util;
+mod validate;
+mod visitor;
 
 use std::char;
-use std::io::Read;
+use std::fs;
+use std::io::{self, BufRead, Read, Write};
+use std::path::Path;
+use std::str::FromStr;
 
-pub use error::ParseError;
-pub use section::{Entry, Section, Value};
+pub use addreg::{AddRegFlags, AddRegRow, RegRoot};
+pub use borrowed::{EntryRef, InfRef, SectionRef, ValueRef};
+pub use decoded::{DecodeWarning, DecodedText};
+pub use diff::{EntryChange, InfDiff, SectionDiff};
+pub use disks::DiskInfo;
+pub use error::{ContextualParseError, EditError, MalformedEntryError, ParseError};
+pub use files::FileReference;
+pub use guid::Guid;
+pub use manufacturers::{Manufacturer, Model};
+pub use options::{AnsiCodePage, Encoding, ParseLimit, ParserOptions};
+pub use parser::Parser;
+pub use section::{Entry, MergeStrategy, Section, Value};
+pub use stats::InfStats;
+pub use validate::{ValidationOptions, ValidationWarning};
+pub use visitor::Visitor;
 
-use crate::parser::Parser;
+use crate::decoded::Utf16Decoder;
+use crate::util::{ExpandVarsError, expand_vars};
 
 /// The Byte Order Mark (BOM) is used to signal the endianness of an encoding.
 /// The order `FF FE` strongly suggests that the data is encoded using little-endian byte order.
@@ -26,6 +63,14 @@ use crate::parser::Parser;
 /// <https://en.wikipedia.org/wiki/Byte_order_mark>
 const BOM_LE: [u8; 2] = [0xFF, 0xFE];
 
+/// The order `FE FF` suggests that the data is encoded using big-endian byte order. Windows
+/// does not read INF files saved this way, but they are accepted for other producers.
+const BOM_BE: [u8; 2] = [0xFE, 0xFF];
+
+/// The character a BOM decodes to. Files that were concatenated together may contain a stray
+/// BOM partway through, which is not meaningful content.
+const BOM_CHAR: char = '\u{FEFF}';
+
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct Inf {
     // Using `Vec` instead of `HashMap` to preserve ordering.
@@ -33,6 +78,12 @@ pub struct Inf {
 }
 
 impl Inf {
+    /// Reads all bytes from `reader` and parses them as an INF file.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ParseError::ReadFailure`] if reading from `reader` fails, or any other
+    /// [`ParseError`] if the data is not a valid INF file.
     pub fn from_reader<R>(reader: &mut R) -> Result<Self, ParseError>
     where
         R: Read,
@@ -45,15 +96,199 @@ impl Inf {
         Self::try_from(buffer.as_slice())
     }
 
+    /// Reads the file at `path` and parses it as an INF file.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ParseError::ReadFailure`] if the file cannot be opened or read, or any other
+    /// [`ParseError`] if the data is not a valid INF file.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use inf::Inf;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let inf = Inf::from_path(r"C:\Windows\INF\machine.inf")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_path<P>(path: P) -> Result<Self, ParseError>
+    where
+        P: AsRef<Path>,
+    {
+        let buffer = fs::read(path).map_err(|err| ParseError::ReadFailure { source: err })?;
+
+        Self::try_from(buffer.as_slice())
+    }
+
+    /// Reads `reader` in chunks as they become available and parses the data as an INF file.
+    ///
+    /// UTF-16 data is decoded as each chunk is read rather than after the whole file has
+    /// been read, including code units and surrogate pairs that are split across chunks.
+    /// The decoded text is still parsed in one pass once the end of `reader` is reached.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ParseError::ReadFailure`] if reading from `reader` fails, or any other
+    /// [`ParseError`] if the data is not a valid INF file.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::BufReader;
+    ///
+    /// use inf::Inf;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let buffer = b"\xFF\xFE[\x00A\x00]\x00";
+    /// let inf = Inf::from_buf_read(BufReader::with_capacity(3, buffer.as_slice()))?;
+    /// assert!(inf.has_section("A"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_buf_read<R>(mut reader: R) -> Result<Self, ParseError>
+    where
+        R: BufRead,
+    {
+        let options = ParserOptions::default();
+        let mut decoder = None::<Utf16Decoder>;
+        // Bytes that have not been decoded yet: the start of the file until it is known whether
+        // it begins with a BOM, or all of it if it does not.
+        let mut pending = Vec::new();
+        let mut text = String::new();
+
+        loop {
+            let chunk = match reader.fill_buf() {
+                Ok([]) => break,
+                Ok(chunk) => chunk,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(ParseError::ReadFailure { source: err }),
+            };
+            let len = chunk.len();
+
+            if let Some(decoder) = decoder.as_mut() {
+                decoder.push(chunk, &options, &mut text)?;
+            } else {
+                pending.extend_from_slice(chunk);
+
+                if let Some((mut utf16, rest)) = Utf16Decoder::from_bom(&pending) {
+                    utf16.push(rest, &options, &mut text)?;
+                    decoder = Some(utf16);
+                    pending.clear();
+                }
+            }
+
+            reader.consume(len);
+        }
+
+        match decoder {
+            Some(decoder) => decoder.finish(&options, &mut text)?,
+            None => decoded::decode_ansi(&pending, options.ansi_code_page, &mut text),
+        }
+        let inf = InfRef::parse_with_options(&text, &options)?;
+
+        Ok(inf.into_owned())
+    }
+
+    /// Parses `buffer` as an INF file encoded with either UTF-16 (with BOM) or ANSI.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ParseError`] if the data is not a valid INF file.
     pub fn from_bytes(buffer: &[u8]) -> Result<Self, ParseError> {
         Self::try_from(buffer)
     }
 
+    /// Parses characters that have already been decoded, such as those produced lazily by a
+    /// decoding pipeline.
+    ///
+    /// The characters are collected into a single string before parsing, since entries are
+    /// parsed by looking ahead over the text. Use [`InfRef::parse`] to parse a `&str` without
+    /// copying it.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ParseError`] if the text is not a valid INF file.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use inf::Inf;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let bytes = b"[Version]\nSignature=\"$Chicago$\"";
+    /// let inf = Inf::from_chars(bytes.iter().copied().map(char::from))?;
+    /// assert_eq!(inf.get_string("Version", "Signature"), Some("$Chicago$"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_chars<I>(chars: I) -> Result<Self, ParseError>
+    where
+        I: IntoIterator<Item = char>,
+    {
+        chars.into_iter().collect::<String>().parse()
+    }
+
+    /// Parses `buffer` as an INF file, using `options` to control decoding and parsing.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ParseError`] if the data is not a valid INF file.
+    pub fn from_bytes_with_options(
+        buffer: &[u8],
+        options: &ParserOptions,
+    ) -> Result<Self, ParseError> {
+        let text = decode_data(buffer, options)?;
+        let inf = InfRef::parse_with_options(&text, options)?;
+
+        Ok(inf.into_owned())
+    }
+
+    /// Parses `buffer` as an INF file, skipping anything that cannot be parsed instead of
+    /// failing on the first error.
+    ///
+    /// This is meant for analyzing many files at once, where partial results are more useful
+    /// than none. See [`Parser::parse_lenient`] for how each error is recovered from. If the
+    /// data cannot be decoded, the `Inf` is empty and the decoding error is the only one
+    /// returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use inf::Inf;
+    ///
+    /// let buffer = b"[Version]\nSignature = \"$Chicago$\"x\nClass = Net\n[Strings]";
+    /// let (inf, errors) = Inf::parse_lenient(buffer);
+    /// assert_eq!(inf.get_string("Version", "Class"), Some("Net"));
+    /// assert!(inf.has_section("Strings"));
+    /// assert_eq!(errors.len(), 1);
+    /// ```
+    #[must_use]
+    pub fn parse_lenient(buffer: &[u8]) -> (Self, Vec<ParseError>) {
+        let options = ParserOptions::default();
+        let text = match decode_data(buffer, &options) {
+            Ok(text) => text,
+            Err(err) => return (Self::default(), vec![err]),
+        };
+        let (inf, errors) = Parser::new(&text, options).parse_lenient();
+
+        (inf.into_owned(), errors)
+    }
+
     #[must_use]
     pub fn sections(&self) -> &[Section] {
         &self.sections
     }
 
+    /// Returns `true` if the file has no sections.
+    ///
+    /// A file whose sections are all empty is not itself empty; see [`Section::is_empty`].
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.sections.is_empty()
+    }
+
     /// Returns the first section whose name matches `name`, ignoring ASCII case.
     ///
     /// This function iterates over each section from the top of the INF file to the bottom.
@@ -86,275 +321,2551 @@ impl Inf {
             .iter()
             .find(|section| name.eq_ignore_ascii_case(section.name()))
     }
-}
 
-impl TryFrom<&[u8]> for Inf {
-    type Error = ParseError;
+    /// Returns the name of each section in the order they first appear in the INF file.
+    ///
+    /// Sections whose names only differ by ASCII case are merged into the first one while
+    /// parsing (and by [`Inf::merge`]), so each name is unique and spelled as it was first
+    /// written.
+    #[must_use]
+    pub fn section_names(&self) -> Vec<&str> {
+        self.sections.iter().map(Section::name).collect()
+    }
+
+    /// Returns `true` if a section's name matches `name`, ignoring ASCII case.
+    #[must_use]
+    pub fn has_section(&self, name: &str) -> bool {
+        self.get(name).is_some()
+    }
+
+    /// Returns every section whose base name (see [`Section::base_name`]) matches `base`,
+    /// ignoring ASCII case, such as each decorated variant of an install section.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use inf::Inf;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let inf = Inf::from_bytes(b"[Install.NT]\n[Other]\n[install.NTamd64.Services]")?;
+    /// let names = inf.sections_with_base("Install").iter().map(|s| s.name()).collect::<Vec<_>>();
+    /// assert_eq!(names, ["Install.NT", "install.NTamd64.Services"]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn sections_with_base(&self, base: &str) -> Vec<&Section> {
+        self.sections
+            .iter()
+            .filter(|section| section.base_name().eq_ignore_ascii_case(base))
+            .collect()
+    }
+
+    /// Returns every section whose name matches the regular expression `re`.
+    ///
+    /// Sections are returned in the order they appear in the INF file. Unlike [`Inf::get`], the
+    /// comparison is case-sensitive unless `re` was built with the `(?i)` flag.
+    #[cfg(feature = "regex")]
+    #[must_use]
+    pub fn section_names_matching_regex(&self, re: &regex::Regex) -> Vec<&Section> {
+        self.sections
+            .iter()
+            .filter(|section| re.is_match(section.name()))
+            .collect()
+    }
+
+    /// Returns the string value of `key` in the section named `section`.
+    ///
+    /// Both names are compared ignoring ASCII case. If either the section or key is missing,
+    /// or the value is a list, `None` is returned instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use inf::Inf;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let inf = Inf::from_bytes(b"[Version]\nSignature=\"$Chicago$\"")?;
+    /// assert_eq!(inf.get_string("version", "signature"), Some("$Chicago$"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn get_string(&self, section: &str, key: &str) -> Option<&str> {
+        self.get(section)?.get(key)?.as_raw()
+    }
+
+    /// Returns the list value of `key` in the section named `section`.
+    ///
+    /// Both names are compared ignoring ASCII case. If either the section or key is missing,
+    /// or the value is not a list, `None` is returned instead.
+    #[must_use]
+    pub fn get_list(&self, section: &str, key: &str) -> Option<&[String]> {
+        self.get(section)?.get(key)?.as_list()
+    }
+
+    /// Returns the values of every `key` in the section named `section`, with `%strkey%`
+    /// tokens expanded using `strings`.
+    ///
+    /// Both names are compared ignoring ASCII case. The elements of list values are returned
+    /// individually, in order. If the section is missing or has no such key, the result is
+    /// empty.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`ExpandVarsError`] if any value fails to expand (see
+    /// [`util::expand_vars`]).
+    pub fn get_all_expanded(
+        &self,
+        section: &str,
+        key: &str,
+        strings: &Section,
+    ) -> Result<Vec<String>, ExpandVarsError> {
+        let Some(section) = self.get(section) else {
+            return Ok(Vec::new());
+        };
+
+        section
+            .get_all(key)
+            .flat_map(Value::as_slice)
+            .map(|value| expand_vars(value, strings))
+            .collect()
+    }
+
+    /// Returns the number of items across all sections whose key matches `key`, ignoring ASCII
+    /// case.
+    #[must_use]
+    pub fn count_keys(&self, key: &str) -> usize {
+        self.sections
+            .iter()
+            .map(|section| section.get_all(key).count())
+            .sum()
+    }
+
+    /// Returns an iterator over the entries of the section named `name`, ignoring ASCII case.
+    ///
+    /// If no section matches the name provided, the iterator yields nothing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use inf::Inf;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let inf = Inf::from_bytes(b"[Version]\nSignature=\"$Chicago$\"")?;
+    /// assert_eq!(inf.entries_in("version").count(), 1);
+    /// assert_eq!(inf.entries_in("Strings").count(), 0);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn entries_in(&self, name: &str) -> impl Iterator<Item = &Entry> {
+        self.get(name)
+            .map(Section::entries)
+            .unwrap_or_default()
+            .iter()
+    }
+
+    /// Renames the section `from` to `to`, merging its entries into the section `to` if one
+    /// already exists.
+    ///
+    /// Names are compared ignoring ASCII case. When merging, the entries of `from` are appended
+    /// to the existing section, which keeps its position and the casing of its name. Otherwise,
+    /// the section is renamed in place. If the edit fails, the `Inf` is left unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EditError::SectionNotFound`] if there is no section named `from`, or
+    /// [`EditError::SectionNameEmpty`], [`EditError::SectionNameTooLong`] or
+    /// [`EditError::SectionNameInvalid`] if `to` is not a valid section name.
+    pub fn rename_and_merge(&mut self, from: &str, to: &str) -> Result<(), EditError> {
+        EditError::check_section_name(to)?;
+
+        // Apply the edit to a copy so that `self` is untouched if any step fails.
+        let mut sections = self.sections.clone();
+
+        let i = sections
+            .iter()
+            .position(|section| from.eq_ignore_ascii_case(section.name()))
+            .ok_or_else(|| EditError::SectionNotFound {
+                name: from.to_owned(),
+            })?;
+        let mut section = sections.remove(i);
+
+        if let Some(target) = sections
+            .iter_mut()
+            .find(|section| to.eq_ignore_ascii_case(section.name()))
+        {
+            target.merge(section, MergeStrategy::Append);
+        } else {
+            section.rename_to(to.to_owned());
+            sections.insert(i, section);
+        }
+
+        self.sections = sections;
+        Ok(())
+    }
+
+    /// Merges the sections of `other` into this `Inf`, such as when applying an overlay file.
+    ///
+    /// Sections of `other` whose names match an existing section (ignoring ASCII case) are
+    /// merged into it according to `strategy`; the remaining sections are appended in order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use inf::{Inf, MergeStrategy};
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut base = Inf::from_bytes(b"[Strings]\nName = Base\nVersion = 1")?;
+    /// let overlay = Inf::from_bytes(b"[strings]\nname = Overlay\n[Extra]")?;
+    /// base.merge(overlay, MergeStrategy::Override);
+    ///
+    /// assert_eq!(base.get_string("Strings", "Name"), Some("Overlay"));
+    /// assert_eq!(base.get_string("Strings", "Version"), Some("1"));
+    /// assert!(base.get("Extra").is_some());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn merge(&mut self, other: Inf, strategy: MergeStrategy) {
+        for section in other.sections {
+            if let Some(target) = self
+                .sections
+                .iter_mut()
+                .find(|s| s.name().eq_ignore_ascii_case(section.name()))
+            {
+                target.merge(section, strategy);
+            } else {
+                self.sections.push(section);
+            }
+        }
+    }
+
+    /// Writes the sections to `w` as an INF file that parses back to an equal `Inf`.
+    ///
+    /// Every value is quoted, with quotes and backslashes escaped, so the output is meant to
+    /// be read with the default [`ParserOptions`]. Keys are only quoted when they contain a
+    /// character that would otherwise end or split them. Comments and the original formatting
+    /// are not preserved.
+    ///
+    /// The parser only produces a [`Value::List`] for two or more fields, so a list with fewer
+    /// fields is written as a single field and reads back as a [`Value::Raw`]; an empty list
+    /// reads back as an empty [`Value::Raw`].
+    ///
+    /// # Errors
+    ///
+    /// Returns any error returned by `w`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use inf::Inf;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let inf = Inf::from_bytes(b"[Files]\nfile.sys\n[Install]\nCopyFiles = Files, Extra")?;
+    /// let mut output = Vec::new();
+    /// inf.dump(&mut output)?;
+    ///
+    /// assert_eq!(
+    ///     String::from_utf8(output)?,
+    ///     "[Files]\n\"file.sys\"\n\n[Install]\nCopyFiles = \"Files\",\"Extra\"\n\n"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn dump<W>(&self, w: &mut W) -> io::Result<()>
+    where
+        W: Write,
+    {
+        for section in &self.sections {
+            writeln!(w, "[{}]", section.name())?;
+
+            for entry in section.entries() {
+                if let Some(key) = entry.key() {
+                    if key.is_empty()
+                        || key.starts_with('[')
+                        || key.contains(['=', '"', ',', ';', '\\'])
+                        || key.trim_ascii() != key
+                    {
+                        write!(w, "{} = ", quote(key))?;
+                    } else {
+                        write!(w, "{key} = ")?;
+                    }
+                }
+
+                let values = entry.value().iter().map(quote).collect::<Vec<_>>();
+
+                if values.is_empty() {
+                    // A blank line would be skipped when read back, dropping the entry.
+                    writeln!(w, "\"\"")?;
+                } else {
+                    writeln!(w, "{}", values.join(","))?;
+                }
+            }
+
+            writeln!(w)?;
+        }
+
+        Ok(())
+    }
+
+    /// Formats the sections as a plain INI file, with every item written as `key=value`.
+    ///
+    /// The export is lossy: value-only entries are dropped, lists are joined with commas, and
+    /// values are written without quotes or escapes, so a value containing a comma or a `;`
+    /// will not read back the same. Use this only for consumers that cannot read INF files.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use inf::Inf;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let inf = Inf::from_bytes(b"[Files]\nfile.sys\n[Strings]\nName = \"Device\"")?;
+    /// assert_eq!(inf.to_ini_string(), "[Files]\n\n[Strings]\nName=Device\n");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn to_ini_string(&self) -> String {
+        let mut ini = String::new();
+
+        for (i, section) in self.sections.iter().enumerate() {
+            if i > 0 {
+                ini.push('\n');
+            }
+
+            ini.push('[');
+            ini.push_str(section.name());
+            ini.push_str("]\n");
+
+            for (key, value) in section.items() {
+                ini.push_str(key);
+                ini.push('=');
+                ini.push_str(&value.as_slice().join(","));
+                ini.push('\n');
+            }
+        }
+
+        ini
+    }
+
+    /// Sets the value of `key` in the section named `section`, both ignoring ASCII case.
+    ///
+    /// The value of the first matching item is replaced, or a new item is appended (see
+    /// [`Section::set`]). If there is no such section, one is appended to the end of the file.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EditError::SectionNameEmpty`], [`EditError::SectionNameTooLong`] or
+    /// [`EditError::SectionNameInvalid`] if `section` is not a valid section name, in which
+    /// case nothing is changed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use inf::{Inf, Value};
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut inf = Inf::from_bytes(b"[Strings]\nName = Old")?;
+    /// inf.set("strings", "name", Value::Raw("New".to_owned()))?;
+    /// inf.set("Version", "Signature", Value::Raw("$Windows NT$".to_owned()))?;
+    ///
+    /// assert_eq!(inf.get_string("Strings", "Name"), Some("New"));
+    /// assert_eq!(inf.get_string("Version", "Signature"), Some("$Windows NT$"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set(&mut self, section: &str, key: &str, value: Value) -> Result<(), EditError> {
+        EditError::check_section_name(section)?;
+
+        let i = if let Some(i) = self
+            .sections
+            .iter()
+            .position(|s| section.eq_ignore_ascii_case(s.name()))
+        {
+            i
+        } else {
+            self.sections
+                .push(Section::new(section.to_owned(), Vec::new()));
+            self.sections.len() - 1
+        };
+
+        self.sections[i].set(key, value);
+        Ok(())
+    }
+
+    /// Removes entries that exactly repeat an earlier entry in the same section.
+    ///
+    /// Entries are only considered duplicates if both their keys and values are identical,
+    /// including case. The first occurrence of each entry keeps its position.
+    pub fn dedup_entries(&mut self) {
+        for section in &mut self.sections {
+            section.dedup();
+        }
+    }
+
+    /// Expands `%strkey%` tokens in the keys and values of every section except `[Strings]`.
+    ///
+    /// Tokens are resolved using the entries of the `[Strings]` section (see
+    /// [`util::expand_vars`]). If the file has no `[Strings]` section, every token is treated as
+    /// not found. Nothing is modified unless every token expands successfully.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`ExpandVarsError`] if any key or value fails to expand.
+    pub fn expand_all(&mut self) -> Result<(), ExpandVarsError> {
+        let strings = self
+            .get("Strings")
+            .cloned()
+            .unwrap_or_else(|| Section::new("Strings".to_owned(), Vec::new()));
+        let mut sections = self.sections.clone();
+
+        for section in sections
+            .iter_mut()
+            .filter(|section| !section.name().eq_ignore_ascii_case("Strings"))
+        {
+            for entry in section.entries_mut() {
+                match entry {
+                    Entry::Item(key, value) => {
+                        *key = expand_vars(key, &strings)?;
+                        expand_value(value, &strings)?;
+                    }
+                    Entry::Value(value) => expand_value(value, &strings)?,
+                }
+            }
+        }
+
+        self.sections = sections;
+        Ok(())
+    }
+}
+
+/// Wraps `value` in double quotes, escaping the characters that are un-escaped when parsing.
+fn quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\"\""))
+}
+
+/// Collects sections into an `Inf`, merging sections with the same name (ignoring ASCII
+/// case) as if they were parsed from a single file.
+impl FromIterator<Section> for Inf {
+    fn from_iter<I: IntoIterator<Item = Section>>(iter: I) -> Self {
+        let mut inf = Self::default();
+        inf.merge(
+            Self {
+                sections: iter.into_iter().collect(),
+            },
+            MergeStrategy::Append,
+        );
+        inf
+    }
+}
+
+impl AsRef<[Section]> for Inf {
+    fn as_ref(&self) -> &[Section] {
+        &self.sections
+    }
+}
+
+impl TryFrom<&[u8]> for Inf {
+    type Error = ParseError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        Self::from_bytes_with_options(data, &ParserOptions::default())
+    }
+}
+
+impl FromStr for Inf {
+    type Err = ParseError;
+
+    /// Parses text that has already been decoded, such as an INF file embedded in a program.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use inf::Inf;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let inf = "[Version]\nSignature=\"$Chicago$\"".parse::<Inf>()?;
+    /// assert_eq!(inf.get_string("Version", "Signature"), Some("$Chicago$"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let inf = if text.contains(BOM_CHAR) {
+            InfRef::parse(&text.replace(BOM_CHAR, ""))?.into_owned()
+        } else {
+            InfRef::parse(text)?.into_owned()
+        };
+
+        Ok(inf)
+    }
+}
+
+impl TryFrom<&str> for Inf {
+    type Error = ParseError;
+
+    fn try_from(text: &str) -> Result<Self, Self::Error> {
+        text.parse()
+    }
+}
+
+fn expand_value(value: &mut Value, strings: &Section) -> Result<(), ExpandVarsError> {
+    match value {
+        Value::Raw(s) => *s = expand_vars(s, strings)?,
+        Value::List(values) => {
+            for s in values {
+                *s = expand_vars(s, strings)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Converts a slice of bytes into a UTF-8 string that we can iterate over.
+fn decode_data(data: &[u8], options: &ParserOptions) -> Result<String, ParseError> {
+    let mut text = String::with_capacity(data.len());
+    decoded::decode(data, options, &mut text)?;
+
+    Ok(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn multiline_value_with_inline_comments() {
+        let buffer = b"\
+            [Section]\n\
+            key = value1,\"value2;not-a-comment\"\\ ; This is an inline comment.\n\
+            ,value3,,value5
+        ";
+        let inf = Inf::from_bytes(buffer).expect("failed to parse hardcoded INF file");
+
+        assert_eq!(
+            inf.sections(),
+            &vec![Section::new(
+                "Section".to_owned(),
+                vec![Entry::Item(
+                    "key".to_owned(),
+                    Value::List(vec![
+                        "value1".to_owned(),
+                        "value2;not-a-comment".to_owned(),
+                        "value3".to_owned(),
+                        String::new(),
+                        "value5".to_owned()
+                    ]),
+                )]
+            )]
+        );
+    }
+
+    #[test]
+    fn lines_end_with_crlf() {
+        let buffer = b"\
+            [Version] ; This section is required\r\n\
+            signature = \"$CHICAGO$\"\r\
+        ";
+        let inf = Inf::from_bytes(buffer).expect("failed to parse hardcoded INF file");
+
+        assert_eq!(
+            inf.sections(),
+            &vec![Section::new(
+                "Version".to_owned(),
+                vec![Entry::Item(
+                    "signature".to_owned(),
+                    Value::Raw("$CHICAGO$".to_owned())
+                )]
+            )]
+        );
+    }
+
+    #[test]
+    fn crlf_parses_identically_to_lf() {
+        let lf = "\
+            ; Novella.inf\n\
+            [Version]\n\
+            Signature = \"$Windows NT$\" ; comment\n\
+            \n\
+            [SourceDisksFiles]\n\
+            novella.sys = 1,,\\\n\
+            ,\"drivers\"\n\
+            readme.txt\n\
+        ";
+        let crlf = lf.replace('\n', "\r\n");
+
+        let expected = Inf::from_bytes(lf.as_bytes()).expect("failed to parse hardcoded INF file");
+        let inf = Inf::from_bytes(crlf.as_bytes()).expect("failed to parse hardcoded INF file");
+
+        assert_eq!(inf, expected);
+        assert_eq!(
+            inf.get("SourceDisksFiles").unwrap().entries(),
+            &[
+                Entry::Item(
+                    "novella.sys".to_owned(),
+                    Value::List(vec![
+                        "1".to_owned(),
+                        String::new(),
+                        String::new(),
+                        "drivers".to_owned()
+                    ])
+                ),
+                Entry::Value(Value::Raw("readme.txt".to_owned())),
+            ]
+        );
+    }
+
+    #[test]
+    fn custom_continuation_char() {
+        let buffer = b"\
+            [Section]\n\
+            key = value1,`\n\
+            C:\\path\\\
+        ";
+        let options = ParserOptions::new().continuation_char('`');
+        let inf = Inf::from_bytes_with_options(buffer, &options)
+            .expect("failed to parse hardcoded INF file");
+
+        assert_eq!(
+            inf.sections(),
+            &vec![Section::new(
+                "Section".to_owned(),
+                vec![Entry::Item(
+                    "key".to_owned(),
+                    Value::List(vec!["value1".to_owned(), "C:\\path\\".to_owned()])
+                )]
+            )]
+        );
+    }
+
+    #[test]
+    fn continuation_at_end_of_file() {
+        let buffer = b"\
+            [Section]\n\
+            key = value\\\\\
+        ";
+        let inf = Inf::from_bytes(buffer).expect("failed to parse hardcoded INF file");
+
+        assert_eq!(
+            inf.sections(),
+            &vec![Section::new(
+                "Section".to_owned(),
+                vec![Entry::Item(
+                    "key".to_owned(),
+                    Value::Raw("value\\".to_owned())
+                )]
+            )]
+        );
+    }
+
+    #[test]
+    fn quoted_value_spanning_lines() {
+        let lf = "\
+            [Strings]\n\
+            Message = \"line1\n\
+            line2\" ; comment\n\
+            next = value\
+        ";
+        let crlf = lf.replace('\n', "\r\n");
+
+        for buffer in [lf.as_bytes(), crlf.as_bytes()] {
+            let inf = Inf::from_bytes(buffer).expect("failed to parse hardcoded INF file");
+
+            assert_eq!(
+                inf.sections(),
+                &vec![Section::new(
+                    "Strings".to_owned(),
+                    vec![
+                        Entry::Item("Message".to_owned(), Value::Raw("line1\nline2".to_owned())),
+                        Entry::Item("next".to_owned(), Value::Raw("value".to_owned())),
+                    ]
+                )]
+            );
+        }
+    }
+
+    #[test]
+    fn raw_values_keep_escapes() {
+        let buffer = br#"
+            [Values]
+            Quote = "say ""hi"""
+            Path = "C:\\Windows", plain
+        "#;
+        let options = ParserOptions::new().raw_values(true);
+        let inf = Inf::from_bytes_with_options(buffer, &options)
+            .expect("failed to parse hardcoded INF file");
+
+        assert_eq!(inf.get_string("Values", "Quote"), Some(r#"say ""hi"""#));
+        assert_eq!(
+            inf.get_list("Values", "Path"),
+            Some([r"C:\\Windows".to_owned(), "plain".to_owned()].as_slice())
+        );
+
+        let inf = Inf::from_bytes(buffer).expect("failed to parse hardcoded INF file");
+        assert_eq!(inf.get_string("Values", "Quote"), Some(r#"say "hi""#));
+        assert_eq!(
+            inf.get_list("Values", "Path"),
+            Some([r"C:\Windows".to_owned(), "plain".to_owned()].as_slice())
+        );
+    }
+
+    #[test]
+    fn backslash_within_quotes_is_literal() {
+        let buffer = br#"
+            [Strings]
+            Dir = "C:\Windows\"
+            Path = "path\
+            more"
+            Next = value
+        "#;
+        let inf = Inf::from_bytes(buffer).expect("failed to parse hardcoded INF file");
+
+        assert_eq!(inf.get_string("Strings", "Dir"), Some(r"C:\Windows\"));
+        assert_eq!(
+            inf.get_string("Strings", "Path"),
+            Some("path\\\n            more")
+        );
+        assert_eq!(inf.get_string("Strings", "Next"), Some("value"));
+    }
+
+    #[test]
+    fn doubled_backslashes_collapse_once() {
+        let buffer = br#"
+            [Strings]
+            Dir = C:\\Windows\\System32
+            Odd = a\\\b
+            Quad = a\\\\b
+            Trailing = "C:\\"
+        "#;
+        let inf = Inf::from_bytes(buffer).expect("failed to parse hardcoded INF file");
+
+        assert_eq!(
+            inf.get_string("Strings", "Dir"),
+            Some(r"C:\Windows\System32")
+        );
+        // Pairs are collapsed from the left, so the odd backslash out is kept as written.
+        assert_eq!(inf.get_string("Strings", "Odd"), Some(r"a\\b"));
+        assert_eq!(inf.get_string("Strings", "Quad"), Some(r"a\\b"));
+        // Quoted so that the last backslash is not taken for a line continuation.
+        assert_eq!(inf.get_string("Strings", "Trailing"), Some(r"C:\"));
+    }
+
+    #[test]
+    fn parse_limits() {
+        let buffer = b"\
+            [A]\n\
+            key = a,\\\n\
+            b\n\
+            value\n\
+            [B]\n\
+            [a]\n\
+            other = c\
+        ";
+        let parse = |options: ParserOptions| Inf::from_bytes_with_options(buffer, &options);
+
+        assert!(parse(ParserOptions::new()).is_ok());
+        assert!(parse(ParserOptions::new().max_sections(2)).is_ok());
+        assert!(parse(ParserOptions::new().max_entries_per_section(3)).is_ok());
+        assert!(parse(ParserOptions::new().max_value_len(9)).is_ok());
+
+        for (options, limit) in [
+            (ParserOptions::new().max_sections(1), ParseLimit::Sections),
+            (
+                ParserOptions::new().max_entries_per_section(2),
+                ParseLimit::EntriesPerSection,
+            ),
+            (ParserOptions::new().max_value_len(8), ParseLimit::ValueLen),
+        ] {
+            assert!(
+                matches!(parse(options), Err(ParseError::LimitExceeded { limit: l }) if l == limit),
+                "expected {limit:?} to be exceeded"
+            );
+        }
+    }
+
+    #[test]
+    fn continuation_abuse_is_limited() {
+        for line in ["\\\n", "value,\\\n"] {
+            let mut text = "[Section]\nkey = ".to_owned();
+            text.push_str(&line.repeat(100_000));
+            text.push_str("end");
+
+            assert!(matches!(
+                InfRef::parse(&text),
+                Err(ParseError::LimitExceeded {
+                    limit: ParseLimit::ContinuedLines
+                })
+            ));
+        }
+    }
+
+    #[test]
+    fn escaped_semicolons() {
+        let buffer = br#"
+            [Section]
+            key = a\;b ; comment
+            list = "c\;d", e\;f
+            escaped_backslash = a\\;b
+        "#;
+        let options = ParserOptions::new().escaped_semicolons(true);
+        let inf = Inf::from_bytes_with_options(buffer, &options)
+            .expect("failed to parse hardcoded INF file");
+
+        assert_eq!(inf.get_string("Section", "key"), Some("a;b"));
+        assert_eq!(
+            inf.get_list("Section", "list"),
+            Some([r"c\;d".to_owned(), "e;f".to_owned()].as_slice())
+        );
+
+        // The backslash before the semicolon is itself escaped, so the semicolon starts a
+        // comment. The line then ends with a backslash, which continues it onto the next line.
+        let inf = Inf::from_bytes_with_options(b"[Section]\nkey = a\\\\;b\nc", &options)
+            .expect("failed to parse hardcoded INF file");
+        assert_eq!(inf.get_string("Section", "key"), Some(r"a\c"));
+
+        let options = options.raw_values(true);
+        let inf = Inf::from_bytes_with_options(buffer, &options)
+            .expect("failed to parse hardcoded INF file");
+        assert_eq!(inf.get_string("Section", "key"), Some(r"a\;b"));
+    }
+
+    #[test]
+    fn custom_comment_chars() {
+        let buffer = b"\
+            # leading comment\n\
+            [Section] # header comment\n\
+            key = value # inline comment\n\
+            quoted = \"a # b\"; also a comment\n\
+            # full line comment\
+        ";
+        let options = ParserOptions::new().comment_chars(&[';', '#']);
+        let inf = Inf::from_bytes_with_options(buffer, &options)
+            .expect("failed to parse hardcoded INF file");
+
+        assert_eq!(
+            inf.sections(),
+            &vec![Section::new(
+                "Section".to_owned(),
+                vec![
+                    Entry::Item("key".to_owned(), Value::Raw("value".to_owned())),
+                    Entry::Item("quoted".to_owned(), Value::Raw("a # b".to_owned())),
+                ]
+            )]
+        );
+
+        // `#` is not a comment character by default.
+        assert_eq!(Inf::from_bytes(buffer), Err(ParseError::EntryBeforeSection));
+    }
+
+    #[test]
+    fn stray_bom_is_dropped() {
+        let text = "[Strings]\nName = \"Con\u{FEFF}toso\"\n\u{FEFF}[Version]";
+        let mut utf16 = BOM_LE.to_vec();
+        utf16.extend(text.encode_utf16().flat_map(u16::to_le_bytes));
+
+        for buffer in [text.as_bytes(), utf16.as_slice()] {
+            let inf = Inf::from_bytes(buffer).expect("failed to parse hardcoded INF file");
+
+            assert_eq!(
+                inf.sections(),
+                &vec![
+                    Section::new(
+                        "Strings".to_owned(),
+                        vec![Entry::Item(
+                            "Name".to_owned(),
+                            Value::Raw("Contoso".to_owned())
+                        )]
+                    ),
+                    Section::new("Version".to_owned(), vec![]),
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn from_str_matches_from_bytes() {
+        let text = "\
+            \u{FEFF}[Version]\n\
+            Signature = \"$Windows NT$\"\n\
+            [Strings]\n\
+            key = value1,value2\
+        ";
+        let inf = text
+            .parse::<Inf>()
+            .expect("failed to parse hardcoded INF file");
+
+        assert_eq!(
+            inf,
+            Inf::from_bytes(text.as_bytes()).expect("failed to parse hardcoded INF file")
+        );
+        assert_eq!(Inf::try_from(text).ok(), Some(inf));
+    }
+
+    #[test]
+    fn from_chars_matches_from_str() {
+        let text = "\u{FEFF}[Section]\nkey = \"a\",b\\\nc";
+        let inf = Inf::from_chars(text.chars()).expect("failed to parse hardcoded INF file");
+
+        assert_eq!(Ok(inf), text.parse::<Inf>());
+    }
+
+    #[test]
+    fn on_section_callback() {
+        let text = "\
+            [Version]\n\
+            Signature = \"$Windows NT$\"\n\
+            [Install]\n\
+            CopyFiles = Files\n\
+            [Files]\n\
+            driver.sys\
+        ";
+        let mut count = 0;
+        let inf = Parser::new(text, ParserOptions::default())
+            .on_section(|_| count += 1)
+            .parse()
+            .expect("failed to parse hardcoded INF file");
+
+        assert_eq!(count, inf.sections().len());
+        assert_eq!(count, 3);
+    }
+
+    #[test]
+    fn multiple_sections() {
+        let buffer = b"\
+            [Section1]\n\
+            [Section2]\n\
+            [Section3]\
+        ";
+        let inf = Inf::from_bytes(buffer).expect("failed to parse hardcoded INF file");
+
+        assert_eq!(
+            inf.sections(),
+            vec![
+                Section::new("Section1".to_owned(), vec![]),
+                Section::new("Section2".to_owned(), vec![]),
+                Section::new("Section3".to_owned(), vec![]),
+            ]
+        );
+    }
+
+    #[test]
+    fn multiple_entries() {
+        let buffer = b"\
+            [Section]\n\
+            key1 = value1\n\
+            key2 = value2\n\
+            key3 = value3\
+        ";
+        let inf = Inf::from_bytes(buffer).expect("failed to parse hardcoded INF file");
+
+        assert_eq!(
+            inf.sections(),
+            &vec![Section::new(
+                "Section".to_owned(),
+                vec![
+                    Entry::Item("key1".to_owned(), Value::Raw("value1".to_owned())),
+                    Entry::Item("key2".to_owned(), Value::Raw("value2".to_owned())),
+                    Entry::Item("key3".to_owned(), Value::Raw("value3".to_owned())),
+                ]
+            )]
+        );
+    }
+
+    #[test]
+    fn mixed_entry_kinds() {
+        let buffer = b"\
+            [Section]\n\
+            value\n\
+            \"value1\",value2,,\"value4\\\"\n\
+            key = value\
+        ";
+        let inf = Inf::from_bytes(buffer).expect("failed to parse hardcoded INF file");
+
+        assert_eq!(
+            inf.sections(),
+            &vec![Section::new(
+                "Section".to_owned(),
+                vec![
+                    Entry::Value(Value::Raw("value".to_owned())),
+                    Entry::Value(Value::List(vec![
+                        "value1".to_owned(),
+                        "value2".to_owned(),
+                        String::new(),
+                        "value4\\".to_owned()
+                    ])),
+                    Entry::Item("key".to_owned(), Value::Raw("value".to_owned())),
+                ]
+            )]
+        );
+    }
+
+    #[test]
+    fn comment_only_lines_between_entries() {
+        let buffer = b"\
+            [Section]\n\
+            key1 = value1\n\
+            ; first comment\n\
+            \n\
+            ; second comment\n\
+            \t; indented comment\n\
+            key2 = value2\n\
+            [Other]\n\
+            key3 = value3\
+        ";
+        let inf = Inf::from_bytes(buffer).expect("failed to parse hardcoded INF file");
+
+        assert_eq!(
+            inf.sections(),
+            &vec![
+                Section::new(
+                    "Section".to_owned(),
+                    vec![
+                        Entry::Item("key1".to_owned(), Value::Raw("value1".to_owned())),
+                        Entry::Item("key2".to_owned(), Value::Raw("value2".to_owned())),
+                    ]
+                ),
+                Section::new(
+                    "Other".to_owned(),
+                    vec![Entry::Item(
+                        "key3".to_owned(),
+                        Value::Raw("value3".to_owned())
+                    )]
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn item_value_quoted() {
+        let buffer = b"\
+            [Section]\n\
+            key = \"value\"\
+        ";
+        let inf = Inf::from_bytes(buffer).expect("failed to parse hardcoded INF file");
+
+        assert_eq!(
+            inf.sections(),
+            &vec![Section::new(
+                "Section".to_owned(),
+                vec![Entry::Item(
+                    "key".to_owned(),
+                    Value::Raw("value".to_owned())
+                )]
+            )]
+        );
+    }
+
+    #[test]
+    fn item_value_unquoted() {
+        let buffer = b"\
+            [Section]\n\
+            key = value\n\
+        ";
+        let inf = Inf::from_bytes(buffer).expect("failed to parse hardcoded INF file");
+
+        assert_eq!(
+            inf.sections(),
+            &vec![Section::new(
+                "Section".to_owned(),
+                vec![Entry::Item(
+                    "key".to_owned(),
+                    Value::Raw("value".to_owned())
+                )]
+            )]
+        );
+    }
+
+    #[test]
+    fn item_value_unquoted_with_spaces() {
+        let buffer = b"\
+            [Section]\n\
+            key = unquoted value with spaces\
+        ";
+        let inf = Inf::from_bytes(buffer).expect("failed to parse hardcoded INF file");
+
+        assert_eq!(
+            inf.sections(),
+            &vec![Section::new(
+                "Section".to_owned(),
+                vec![Entry::Item(
+                    "key".to_owned(),
+                    Value::Raw("unquoted value with spaces".to_owned())
+                )]
+            )]
+        );
+    }
+
+    #[test]
+    fn item_value_quoted_with_leading_spaces() {
+        let buffer = b"\
+            [Section]\n\
+            key = \"    with 4 leading spaces\"\
+        ";
+        let inf = Inf::from_bytes(buffer).expect("failed to parse hardcoded INF file");
+
+        assert_eq!(
+            inf.sections(),
+            &vec![Section::new(
+                "Section".to_owned(),
+                vec![Entry::Item(
+                    "key".to_owned(),
+                    Value::Raw("    with 4 leading spaces".to_owned())
+                )]
+            )]
+        );
+    }
+
+    #[test]
+    fn item_value_quoted_with_trailing_spaces() {
+        let buffer = b"\
+            [Section]\n\
+            key = \"with 5 trailing spaces     \"\
+        ";
+        let inf = Inf::from_bytes(buffer).expect("failed to parse hardcoded INF file");
+
+        assert_eq!(
+            inf.sections(),
+            &vec![Section::new(
+                "Section".to_owned(),
+                vec![Entry::Item(
+                    "key".to_owned(),
+                    Value::Raw("with 5 trailing spaces     ".to_owned())
+                )]
+            )]
+        );
+    }
+
+    #[test]
+    fn item_value_quoted_with_equal_sign() {
+        let buffer = b"\
+            [Section]\n\
+            \"1+1=2\"
+        ";
+        let inf = Inf::from_bytes(buffer).expect("failed to parse hardcoded INF file");
+
+        assert_eq!(
+            inf.sections(),
+            &vec![Section::new(
+                "Section".to_owned(),
+                vec![Entry::Value(Value::Raw("1+1=2".to_owned()))]
+            )]
+        );
+    }
+
+    #[test]
+    fn value_list_quoted_element_with_equal_sign() {
+        let buffer = b"\
+            [Section]\n\
+            \"a=b\",c\
+        ";
+        let inf = Inf::from_bytes(buffer).expect("failed to parse hardcoded INF file");
+
+        assert_eq!(
+            inf.sections(),
+            &vec![Section::new(
+                "Section".to_owned(),
+                vec![Entry::Value(Value::list_from(&["a=b", "c"]))]
+            )]
+        );
+    }
+
+    #[test]
+    fn quoted_whitespace_is_preserved() {
+        let buffer = b"\
+            [Section]\n\
+            \"   \"\n\
+            key = \"  padded  \" ,\" \"\
+        ";
+        let inf = Inf::from_bytes(buffer).expect("failed to parse hardcoded INF file");
+
+        assert_eq!(
+            inf.sections(),
+            &vec![Section::new(
+                "Section".to_owned(),
+                vec![
+                    Entry::Value(Value::Raw("   ".to_owned())),
+                    Entry::Item("key".to_owned(), Value::list_from(&["  padded  ", " "])),
+                ]
+            )]
+        );
+    }
+
+    #[test]
+    fn quoted_value_ending_in_escaped_quote() {
+        let buffer = br#"
+            [Strings]
+            Said = "He said ""hi"""
+            Hello = "hello ""world"""
+            Quote = """"
+            Empty = ""
+        "#;
+        let inf = Inf::from_bytes(buffer).expect("failed to parse hardcoded INF file");
+
+        // The outer quotes are stripped before `""` is collapsed, so the last three quotes
+        // are an escaped quote followed by the closing one.
+        assert_eq!(inf.get_string("Strings", "Said"), Some(r#"He said "hi""#));
+        assert_eq!(inf.get_string("Strings", "Hello"), Some(r#"hello "world""#));
+        assert_eq!(inf.get_string("Strings", "Quote"), Some("\""));
+        assert_eq!(inf.get_string("Strings", "Empty"), Some(""));
+    }
+
+    #[test]
+    fn trailing_comma_is_an_empty_field() {
+        let buffer = b"\
+            [Section]\n\
+            trailing = a,b,\n\
+            plain = a,b\n\
+            single = a,\n\
+            a,b,\
+        ";
+        let inf = Inf::from_bytes(buffer).expect("failed to parse hardcoded INF file");
+
+        assert_eq!(
+            inf.sections(),
+            &vec![Section::new(
+                "Section".to_owned(),
+                vec![
+                    Entry::Item("trailing".to_owned(), Value::list_from(&["a", "b", ""])),
+                    Entry::Item("plain".to_owned(), Value::list_from(&["a", "b"])),
+                    Entry::Item("single".to_owned(), Value::list_from(&["a", ""])),
+                    Entry::Value(Value::list_from(&["a", "b", ""])),
+                ]
+            )]
+        );
+    }
+
+    #[test]
+    fn tabs_are_whitespace() {
+        let spaces = b"\
+            [Section] ; comment\n\
+            key = value\n\
+            list = a , b\n\
+            value\
+        ";
+        let tabs = b"\
+            [Section]\t; comment\n\
+            key\t=\tvalue\t\n\
+            list\t=\ta\t,\tb\n\
+            \tvalue\t\
+        ";
+
+        assert_eq!(Inf::from_bytes(tabs), Inf::from_bytes(spaces));
+
+        // Only ASCII whitespace is trimmed, such as the no-break space (U+00A0) in Latin-1.
+        let options = ParserOptions::new().ansi_code_page(AnsiCodePage::Latin1);
+        let inf = Inf::from_bytes_with_options(b"[Section]\nkey = \xA0value\xA0", &options)
+            .expect("failed to parse hardcoded INF file");
+        assert_eq!(inf.get_string("Section", "key"), Some("\u{A0}value\u{A0}"));
+    }
+
+    #[test]
+    fn bare_token_is_value_only() {
+        let buffer = b"\
+            [Section]\n\
+            SomeFlag\
+        ";
+        let inf = Inf::from_bytes(buffer).expect("failed to parse hardcoded INF file");
+
+        assert_eq!(
+            inf.sections(),
+            &vec![Section::new(
+                "Section".to_owned(),
+                vec![Entry::Value(Value::Raw("SomeFlag".to_owned()))]
+            )]
+        );
+    }
+
+    #[test]
+    fn section_header_followed_by_quoted_text() {
+        let buffer = b"\
+            [Sec] \"; not a comment because quoted?\" ; a comment\n\
+            key = value\
+        ";
+        let inf = Inf::from_bytes(buffer).expect("failed to parse hardcoded INF file");
+
+        assert_eq!(inf.section_names(), vec!["Sec"]);
+        assert_eq!(inf.get_string("Sec", "key"), Some("value"));
+
+        let result = Inf::from_bytes(b"[Sec] \"; unterminated\nkey = value");
+
+        assert_eq!(
+            result,
+            Err(ParseError::UnterminatedString {
+                section: Some("Sec".to_owned())
+            })
+        );
+    }
+
+    #[test]
+    fn error_includes_section_name() {
+        let buffer = b"\
+            [Version]\n\
+            Signature = \"$Windows NT$\"\n\
+            [DestinationDirs]\n\
+            DefaultDestDir = 12,\"drivers\n\
+        ";
+        let err = Inf::from_bytes(buffer).expect_err("expected an unterminated string");
+
+        assert_eq!(
+            err,
+            ParseError::UnterminatedString {
+                section: Some("DestinationDirs".to_owned()),
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "unterminated string in section [DestinationDirs]"
+        );
+    }
+
+    #[test]
+    fn duplicate_sections_merge_ignoring_case() {
+        let buffer = b"\
+            [Strings]\n\
+            a = 1\n\
+            [STRINGS]\n\
+            b = 2\
+        ";
+        let inf = Inf::from_bytes(buffer).expect("failed to parse hardcoded INF file");
+
+        assert_eq!(
+            inf.sections(),
+            &vec![Section::new(
+                "Strings".to_owned(),
+                vec![
+                    Entry::Item("a".to_owned(), Value::Raw("1".to_owned())),
+                    Entry::Item("b".to_owned(), Value::Raw("2".to_owned())),
+                ]
+            )]
+        );
+    }
+
+    #[test]
+    fn duplicate_count() {
+        let text = "\
+            [Strings]\n\
+            [Version]\n\
+            [STRINGS]\n\
+            [strings]\
+        ";
+        let inf = InfRef::parse(text).expect("failed to parse hardcoded INF file");
+
+        assert_eq!(inf.get("Strings").unwrap().duplicate_count(), 2);
+        assert_eq!(inf.get("Version").unwrap().duplicate_count(), 0);
+
+        let inf = inf.into_owned();
+        assert_eq!(inf.get("Strings").unwrap().duplicate_count(), 2);
+        assert_eq!(inf.get("Version").unwrap().duplicate_count(), 0);
+        assert_eq!(
+            inf.get("Strings"),
+            Some(&Section::new("Strings".to_owned(), vec![]))
+        );
+    }
+
+    #[test]
+    fn error_uses_original_section_casing() {
+        let buffer = b"\
+            [destinationdirs]\n\
+            DefaultDestDir = 12\n\
+            [DestinationDirs]\n\
+            Files.Copy = 12,\"drivers\n\
+        ";
+        let err = Inf::from_bytes(buffer).expect_err("expected an unterminated string");
+
+        assert_eq!(
+            err.to_string(),
+            "unterminated string in section [DestinationDirs]"
+        );
+    }
+
+    #[test]
+    fn section_name_brackets() {
+        let inf = Inf::from_bytes(b"[Good] ; [not] part of the name")
+            .expect("failed to parse hardcoded INF file");
+        assert_eq!(
+            inf.sections(),
+            &vec![Section::new("Good".to_owned(), vec![])]
+        );
+
+        assert!(matches!(
+            Inf::from_bytes(b"[Bad]extra"),
+            Err(ParseError::UnexpectedCharacter { c: 'e', .. })
+        ));
+
+        for (buffer, expected) in [
+            (b"[Nested]]".as_slice(), "Nested]"),
+            (b"[Sec]tion] ; comment".as_slice(), "Sec]tion"),
+        ] {
+            let err = Inf::from_bytes(buffer).expect_err("expected an invalid section name");
+
+            assert!(
+                matches!(err, ParseError::SectionNameInvalid { ref name } if name == expected),
+                "unexpected error: {err:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn section_header_comment_containing_quotes() {
+        let buffer = b"\
+            [Sec] ; a \"quoted\" comment with an \"unbalanced quote\n\
+            key = value\
+        ";
+        let inf = Inf::from_bytes(buffer).expect("failed to parse hardcoded INF file");
+
+        assert_eq!(
+            inf.sections(),
+            &vec![Section::new(
+                "Sec".to_owned(),
+                vec![Entry::Item(
+                    "key".to_owned(),
+                    Value::Raw("value".to_owned())
+                )]
+            )]
+        );
+    }
+
+    #[test]
+    fn custom_replacement_char() {
+        let mut buffer = BOM_LE.to_vec();
+        for unit in "[Strings]\nName = \"a?b\"".encode_utf16() {
+            // Replace the placeholder with a lone surrogate, which is not valid UTF-16.
+            let unit = if unit == u16::from(b'?') {
+                0xD800
+            } else {
+                unit
+            };
+            buffer.extend(unit.to_le_bytes());
+        }
+
+        for (replacement, expected) in [
+            (Some(char::REPLACEMENT_CHARACTER), "a\u{FFFD}b"),
+            (Some('?'), "a?b"),
+            (None, "ab"),
+        ] {
+            let options = ParserOptions::new().replacement_char(replacement);
+            let inf = Inf::from_bytes_with_options(&buffer, &options)
+                .expect("failed to parse hardcoded INF file");
+
+            assert_eq!(inf.get_string("Strings", "Name"), Some(expected));
+        }
+    }
+
+    #[test]
+    fn strict_utf16() {
+        let mut buffer = BOM_LE.to_vec();
+        for unit in "[Strings]\nName = \"a?b?\"".encode_utf16() {
+            let unit = if unit == u16::from(b'?') {
+                0xDC00
+            } else {
+                unit
+            };
+            buffer.extend(unit.to_le_bytes());
+        }
+
+        let options = ParserOptions::new().strict_utf16(true);
+        assert_eq!(
+            Inf::from_bytes_with_options(&buffer, &options),
+            Err(ParseError::InvalidUtf16 { offset: 40 })
+        );
+
+        let inf = Inf::from_bytes(&buffer).expect("failed to parse hardcoded INF file");
+        assert_eq!(
+            inf.get_string("Strings", "Name"),
+            Some("a\u{FFFD}b\u{FFFD}")
+        );
+
+        let decoded = DecodedText::new(&buffer, &ParserOptions::default());
+        assert_eq!(
+            decoded.warnings(),
+            &[
+                DecodeWarning::InvalidUtf16 { offset: 40 },
+                DecodeWarning::InvalidUtf16 { offset: 44 }
+            ]
+        );
+    }
+
+    #[test]
+    fn ansi_code_page_shift_jis() {
+        let buffer = b"[Strings]\nName = \"\x82\xd9\x82\xb5\x82\xdc\x82\xbf\"";
+        let options = ParserOptions::new().ansi_code_page(AnsiCodePage::ShiftJis);
+        let inf = Inf::from_bytes_with_options(buffer, &options)
+            .expect("failed to parse hardcoded INF file");
+
+        assert_eq!(
+            inf.sections(),
+            &vec![Section::new(
+                "Strings".to_owned(),
+                vec![Entry::Item(
+                    "Name".to_owned(),
+                    Value::Raw("ほしまち".to_owned())
+                )]
+            )]
+        );
+    }
+
+    #[test]
+    fn ansi_code_page_windows_1252_and_latin1() {
+        let buffer = b"[Strings]\nPrice = \"5\x80\"";
+
+        let options = ParserOptions::new().ansi_code_page(AnsiCodePage::Windows1252);
+        let inf = Inf::from_bytes_with_options(buffer, &options)
+            .expect("failed to parse hardcoded INF file");
+        assert_eq!(
+            inf.sections()[0].entries(),
+            &[Entry::Item(
+                "Price".to_owned(),
+                Value::Raw("5\u{20AC}".to_owned())
+            )]
+        );
+
+        let options = ParserOptions::new().ansi_code_page(AnsiCodePage::Latin1);
+        let inf = Inf::from_bytes_with_options(buffer, &options)
+            .expect("failed to parse hardcoded INF file");
+        assert_eq!(
+            inf.sections()[0].entries(),
+            &[Entry::Item(
+                "Price".to_owned(),
+                Value::Raw("5\u{80}".to_owned())
+            )]
+        );
+    }
+
+    #[test]
+    fn strings_values_are_never_lists() {
+        let buffer = b"\
+            [Strings]\n\
+            path = C:\\a,C:\\b\n\
+            equation = a=b, c\n\
+            [strings.0409]\n\
+            path = C:\\a, C:\\b\n\
+            [Install]\n\
+            CopyFiles = a,b\
+        ";
+        let inf = Inf::from_bytes(buffer).expect("failed to parse hardcoded INF file");
+
+        assert_eq!(inf.get_string("Strings", "path"), Some(r"C:\a,C:\b"));
+        assert_eq!(inf.get_string("Strings", "equation"), Some("a=b, c"));
+        assert_eq!(inf.get_string("Strings.0409", "path"), Some(r"C:\a, C:\b"));
+        assert_eq!(
+            inf.get_list("Install", "CopyFiles"),
+            Some(["a".to_owned(), "b".to_owned()].as_slice())
+        );
+    }
+
+    #[test]
+    fn strings_section_only() {
+        let buffer = b"\
+            [Strings]\n\
+            Provider = \"Contoso\"\n\
+            DiskName = \"Contoso Installation Disk\"\
+        ";
+        let mut inf = Inf::from_bytes(buffer).expect("failed to parse hardcoded INF file");
+        let expected = vec![Section::new(
+            "Strings".to_owned(),
+            vec![
+                Entry::Item("Provider".to_owned(), Value::Raw("Contoso".to_owned())),
+                Entry::Item(
+                    "DiskName".to_owned(),
+                    Value::Raw("Contoso Installation Disk".to_owned()),
+                ),
+            ],
+        )];
+        assert_eq!(inf.sections(), &expected);
+
+        inf.expand_all().expect("expected expansion to be a no-op");
+        assert_eq!(inf.sections(), &expected);
+    }
+
+    #[test]
+    fn expand_all_sections() {
+        let buffer = b"\
+            [Manufacturer]\n\
+            %Mfg% = Models,NTamd64\n\
+            [Strings]\n\
+            Mfg = \"Contoso\"\
+        ";
+        let mut inf = Inf::from_bytes(buffer).expect("failed to parse hardcoded INF file");
+        inf.expand_all()
+            .expect("expected hardcoded tokens to be valid");
+
+        assert_eq!(
+            inf.get("Manufacturer").unwrap().entries(),
+            &[Entry::Item(
+                "Contoso".to_owned(),
+                Value::List(vec!["Models".to_owned(), "NTamd64".to_owned()])
+            )]
+        );
+    }
+
+    #[test]
+    fn percent_in_comment_is_not_expanded() {
+        let buffer = b"\
+            ; 50% done\n\
+            [Install]\n\
+            CopyFiles = %Files% ; 50% of the %files\n\
+            [Strings] ; 100%\n\
+            Files = \"Files.Copy\" ;%\
+        ";
+        let mut inf = Inf::from_bytes(buffer).expect("failed to parse hardcoded INF file");
+        inf.expand_all()
+            .expect("expected comments to be stripped before expansion");
+
+        assert_eq!(inf.get_string("Install", "CopyFiles"), Some("Files.Copy"));
+    }
+
+    #[test]
+    fn sections_with_base() {
+        let inf = Inf::from_bytes(b"[Install]\n[Install.NT]\n[Installer]\n[INSTALL.NTamd64]")
+            .expect("failed to parse hardcoded INF file");
+        let names = |base| {
+            inf.sections_with_base(base)
+                .into_iter()
+                .map(Section::name)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            names("install"),
+            vec!["Install", "Install.NT", "INSTALL.NTamd64"]
+        );
+        assert_eq!(names("Installer"), vec!["Installer"]);
+        assert_eq!(names("Install.NT"), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn section_names_and_has_section() {
+        let buffer = b"\
+            [Version]\n\
+            [Strings]\n\
+            [VERSION]\n\
+            [Install]\
+        ";
+        let inf = Inf::from_bytes(buffer).expect("failed to parse hardcoded INF file");
+
+        assert_eq!(inf.section_names(), vec!["Version", "Strings", "Install"]);
+        assert!(inf.has_section("version"));
+        assert!(inf.has_section("INSTALL"));
+        assert!(!inf.has_section("Manufacturer"));
+    }
+
+    #[test]
+    fn get_all_expanded() {
+        let buffer = b"\
+            [Install]\n\
+            CopyFiles = %Files%\n\
+            AddReg = Reg\n\
+            copyfiles = %Files%.Extra, Other\n\
+            [Strings]\n\
+            Files = \"Driver\"\
+        ";
+        let inf = Inf::from_bytes(buffer).expect("failed to parse hardcoded INF file");
+        let strings = inf.get("Strings").unwrap();
+
+        assert_eq!(
+            inf.get_all_expanded("install", "CopyFiles", strings),
+            Ok(vec![
+                "Driver".to_owned(),
+                "Driver.Extra".to_owned(),
+                "Other".to_owned()
+            ])
+        );
+        assert_eq!(
+            inf.get_all_expanded("Install", "DelFiles", strings),
+            Ok(vec![])
+        );
+        assert_eq!(
+            inf.get_all_expanded(
+                "Install",
+                "CopyFiles",
+                &Section::new("Strings".to_owned(), vec![])
+            ),
+            Err(ExpandVarsError::NotFound)
+        );
+    }
+
+    #[test]
+    fn count_keys() {
+        let buffer = b"\
+            [Install]\n\
+            CopyFiles = A\n\
+            copyfiles = B\n\
+            AddReg = C\n\
+            [Install.NT]\n\
+            COPYFILES = D\n\
+            CopyFiles\n\
+            [Strings]\
+        ";
+        let inf = Inf::from_bytes(buffer).expect("failed to parse hardcoded INF file");
+
+        assert_eq!(inf.count_keys("CopyFiles"), 3);
+        assert_eq!(inf.count_keys("addreg"), 1);
+        assert_eq!(inf.count_keys("DelFiles"), 0);
+    }
+
+    #[test]
+    fn entries_in_existing_and_absent_sections() {
+        let buffer = b"\
+            [Section]\n\
+            key1 = value1\n\
+            value2\
+        ";
+        let inf = Inf::from_bytes(buffer).expect("failed to parse hardcoded INF file");
+
+        assert_eq!(
+            inf.entries_in("SECTION").collect::<Vec<_>>(),
+            vec![
+                &Entry::Item("key1".to_owned(), Value::Raw("value1".to_owned())),
+                &Entry::Value(Value::Raw("value2".to_owned())),
+            ]
+        );
+        assert_eq!(inf.entries_in("Absent").next(), None);
+    }
+
+    #[test]
+    fn get_string_and_list() {
+        let buffer = b"\
+            [Version]\n\
+            Signature = \"$Windows NT$\"\n\
+            [DefaultInstall]\n\
+            CopyFiles = Files.Copy,Files.Extra\
+        ";
+        let inf = Inf::from_bytes(buffer).expect("failed to parse hardcoded INF file");
+
+        assert_eq!(inf.get_string("VERSION", "signature"), Some("$Windows NT$"));
+        assert_eq!(inf.get_string("Version", "Provider"), None);
+        assert_eq!(inf.get_string("DefaultInstall", "CopyFiles"), None);
+        assert_eq!(
+            inf.get_list("defaultinstall", "copyfiles"),
+            Some(["Files.Copy".to_owned(), "Files.Extra".to_owned()].as_slice())
+        );
+        assert_eq!(inf.get_list("Version", "Signature"), None);
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn section_names_matching_regex() {
+        let buffer = b"\
+            [Version]\n\
+            [Install.NT]\n\
+            [Install.NTamd64]\n\
+            [Install.Services]\n\
+            [Install.NT.Services]\
+        ";
+        let inf = Inf::from_bytes(buffer).expect("failed to parse hardcoded INF file");
+        let re =
+            regex::Regex::new(r"^Install\.NT.*").expect("expected hardcoded regex to be valid");
+
+        assert_eq!(
+            inf.section_names_matching_regex(&re)
+                .iter()
+                .map(|section| section.name())
+                .collect::<Vec<_>>(),
+            vec!["Install.NT", "Install.NTamd64", "Install.NT.Services"]
+        );
+    }
+
+    #[test]
+    fn mismatched_quotes() {
+        for buffer in [
+            b"[Section]\nkey = \"foo".as_slice(),
+            b"[Section]\nkey = foo\"".as_slice(),
+            b"[Section]\nkey = \"foo\"bar".as_slice(),
+            b"[Section]\nkey = foo\"bar\"".as_slice(),
+        ] {
+            let result = Inf::from_bytes(buffer);
+
+            assert!(
+                matches!(result, Err(ParseError::UnterminatedString { .. })),
+                "expected {:?} to be rejected",
+                String::from_utf8_lossy(buffer)
+            );
+        }
+
+        let inf = Inf::from_bytes(b"[Section]\nkey = \"foo\"")
+            .expect("failed to parse hardcoded INF file");
+        assert_eq!(inf.get_string("Section", "key"), Some("foo"));
+    }
+
+    #[test]
+    fn unclosed_quote_is_reported_end_to_end() {
+        for buffer in [
+            b"[Section]\nkey = \"abc".as_slice(),
+            b"[Section]\nkey = \"abc\r\nother = value\r\n[Next]\r\n".as_slice(),
+        ] {
+            assert_eq!(
+                Inf::from_bytes(buffer),
+                Err(ParseError::UnterminatedString {
+                    section: Some("Section".to_owned())
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn keys_are_trimmed_but_otherwise_verbatim() {
+        let buffer = b"\
+            [Section]\n\
+            \t  spaced   key  = v\n\
+            escaped\"\"quote = v\
+        ";
+        let inf = Inf::from_bytes(buffer).expect("failed to parse hardcoded INF file");
+
+        assert_eq!(
+            inf.get("Section").unwrap().entries(),
+            &[
+                Entry::Item("spaced   key".to_owned(), Value::Raw("v".to_owned())),
+                Entry::Item("escaped\"\"quote".to_owned(), Value::Raw("v".to_owned())),
+            ]
+        );
+    }
+
+    #[test]
+    fn empty_key() {
+        for buffer in [&b"[Section]\n= v"[..], b"[Section]\n  \t= v"] {
+            assert_eq!(
+                Inf::from_bytes(buffer),
+                Err(ParseError::EmptyKey {
+                    section: Some("Section".to_owned())
+                })
+            );
+        }
+
+        let inf = Inf::from_bytes(b"[Section]\nk = v\n\"\" = quoted")
+            .expect("failed to parse hardcoded INF file");
+
+        assert_eq!(
+            inf.get("Section").unwrap().entries(),
+            &[
+                Entry::Item("k".to_owned(), Value::Raw("v".to_owned())),
+                Entry::Item(String::new(), Value::Raw("quoted".to_owned())),
+            ]
+        );
+    }
+
+    #[test]
+    fn quoted_key() {
+        let buffer = b"\
+            [Section]\n\
+            \"quoted\" = v\n\
+            \"weird=key\" = v\n\
+            \"say \"\"hi\"\"\" = v\
+        ";
+        let inf = Inf::from_bytes(buffer).expect("failed to parse hardcoded INF file");
+
+        assert_eq!(
+            inf.get("Section").unwrap().entries(),
+            &[
+                Entry::Item("quoted".to_owned(), Value::Raw("v".to_owned())),
+                Entry::Item("weird=key".to_owned(), Value::Raw("v".to_owned())),
+                Entry::Item("say \"hi\"".to_owned(), Value::Raw("v".to_owned())),
+            ]
+        );
+
+        // A quote that does not wrap the whole key is still rejected.
+        let result = Inf::from_bytes(b"[Section]\n\"quoted\"key = v");
+
+        assert!(matches!(
+            result,
+            Err(ParseError::UnexpectedCharacter { c: '"', .. })
+        ));
+
+        // So is an unescaped quote between the outer quotes, whether or not the quotes on the
+        // line are balanced.
+        let result = Inf::from_bytes(b"[Section]\n\"a\"b\" = v");
+
+        assert!(matches!(result, Err(ParseError::UnterminatedString { .. })));
+
+        let result = Inf::from_bytes(b"[Section]\n\"a\"b\"\" = v");
+
+        assert!(matches!(
+            result,
+            Err(ParseError::UnexpectedCharacter { c: '"', .. })
+        ));
+    }
+
+    #[test]
+    fn rename_and_merge() {
+        let buffer = b"\
+            [Install]\n\
+            CopyFiles = Files.A\n\
+            [Install.NT]\n\
+            AddReg = Reg.A\n\
+            [Files.Old]\n\
+            old.sys\
+        ";
+        let mut inf = Inf::from_bytes(buffer).expect("failed to parse hardcoded INF file");
+
+        inf.rename_and_merge("install", "INSTALL.nt")
+            .expect("expected both sections to exist");
+        inf.rename_and_merge("Files.Old", "Files.New")
+            .expect("expected the section to exist");
+
+        assert_eq!(
+            inf.sections(),
+            &vec![
+                Section::new(
+                    "Install.NT".to_owned(),
+                    vec![
+                        Entry::Item("AddReg".to_owned(), Value::Raw("Reg.A".to_owned())),
+                        Entry::Item("CopyFiles".to_owned(), Value::Raw("Files.A".to_owned())),
+                    ]
+                ),
+                Section::new(
+                    "Files.New".to_owned(),
+                    vec![Entry::Value(Value::Raw("old.sys".to_owned()))]
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn rename_and_merge_failure_leaves_inf_unchanged() {
+        let buffer = b"\
+            [Install]\n\
+            CopyFiles = Files.A\
+        ";
+        let mut inf = Inf::from_bytes(buffer).expect("failed to parse hardcoded INF file");
+        let original = inf.clone();
+
+        assert_eq!(
+            inf.rename_and_merge("Missing", "Install"),
+            Err(EditError::SectionNotFound {
+                name: "Missing".to_owned()
+            })
+        );
+        assert_eq!(
+            inf.rename_and_merge("Install", ""),
+            Err(EditError::SectionNameEmpty)
+        );
+        assert_eq!(
+            inf.rename_and_merge("Install", &"a".repeat(256)),
+            Err(EditError::SectionNameTooLong)
+        );
+        assert_eq!(
+            inf.rename_and_merge("Install", "Install]2"),
+            Err(EditError::SectionNameInvalid {
+                name: "Install]2".to_owned()
+            })
+        );
+        assert_eq!(inf, original);
+    }
+
+    #[test]
+    fn merge_append_and_override() {
+        let base = b"\
+            [Install]\n\
+            CopyFiles = Files.A\n\
+            AddReg = Reg.A\
+        ";
+        let overlay = b"\
+            [INSTALL]\n\
+            copyfiles = Files.B\n\
+            DelFiles = Files.C\n\
+            extra.sys\n\
+            [Strings]\
+        ";
+        let base = Inf::from_bytes(base).expect("failed to parse hardcoded INF file");
+        let overlay = Inf::from_bytes(overlay).expect("failed to parse hardcoded INF file");
 
-    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
-        let text = decode_data(data);
-        let parser = Parser::new(&text);
-        let sections = parser.into_sections()?;
+        let mut appended = base.clone();
+        appended.merge(overlay.clone(), MergeStrategy::Append);
+        assert_eq!(
+            appended.sections(),
+            &vec![
+                Section::new(
+                    "Install".to_owned(),
+                    vec![
+                        Entry::Item("CopyFiles".to_owned(), Value::Raw("Files.A".to_owned())),
+                        Entry::Item("AddReg".to_owned(), Value::Raw("Reg.A".to_owned())),
+                        Entry::Item("copyfiles".to_owned(), Value::Raw("Files.B".to_owned())),
+                        Entry::Item("DelFiles".to_owned(), Value::Raw("Files.C".to_owned())),
+                        Entry::Value(Value::Raw("extra.sys".to_owned())),
+                    ]
+                ),
+                Section::new("Strings".to_owned(), vec![]),
+            ]
+        );
 
-        Ok(Self { sections })
+        let mut overridden = base;
+        overridden.merge(overlay, MergeStrategy::Override);
+        assert_eq!(
+            overridden.sections(),
+            &vec![
+                Section::new(
+                    "Install".to_owned(),
+                    vec![
+                        Entry::Item("CopyFiles".to_owned(), Value::Raw("Files.B".to_owned())),
+                        Entry::Item("AddReg".to_owned(), Value::Raw("Reg.A".to_owned())),
+                        Entry::Item("DelFiles".to_owned(), Value::Raw("Files.C".to_owned())),
+                        Entry::Value(Value::Raw("extra.sys".to_owned())),
+                    ]
+                ),
+                Section::new("Strings".to_owned(), vec![]),
+            ]
+        );
     }
-}
 
-/// Converts a slice of bytes into a UTF-8 string that we can iterate over.
-fn decode_data(data: &[u8]) -> String {
-    // INF files must be saved with UTF-16 LE or ANSI file encodings. Because ANSI is a subset
-    // of UTF-8 and endianness is irrelevant to UTF-8, the BOM being present strongly suggests
-    // that the data was encoded with UTF-16 LE.
-    if data.starts_with(&BOM_LE) {
-        let utf16 = data[BOM_LE.len()..]
-            .chunks_exact(2)
-            .map(|chunk| u16::from_le_bytes([chunk[0], chunk[1]]))
-            .collect::<Vec<u16>>();
-
-        char::decode_utf16(utf16)
-            .map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
-            .collect::<String>()
-    } else {
-        String::from_utf8_lossy(data).to_string()
-    }
-}
+    #[test]
+    fn set_creates_missing_sections() {
+        let mut inf = Inf::from_bytes(b"[Version]\nSignature = \"$Chicago$\"\n[Strings]")
+            .expect("failed to parse hardcoded INF file");
+        inf.set(
+            "version",
+            "signature",
+            Value::Raw("$Windows NT$".to_owned()),
+        )
+        .expect("hardcoded section name is valid");
+        inf.set("Strings", "Name", Value::Raw("Device".to_owned()))
+            .expect("hardcoded section name is valid");
+        inf.set(
+            "Install",
+            "CopyFiles",
+            Value::list_from(&["Files", "Extra"]),
+        )
+        .expect("hardcoded section name is valid");
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+        assert_eq!(
+            inf.sections(),
+            &vec![
+                Section::new(
+                    "Version".to_owned(),
+                    vec![Entry::Item(
+                        "Signature".to_owned(),
+                        Value::Raw("$Windows NT$".to_owned())
+                    )]
+                ),
+                Section::new(
+                    "Strings".to_owned(),
+                    vec![Entry::Item(
+                        "Name".to_owned(),
+                        Value::Raw("Device".to_owned())
+                    )]
+                ),
+                Section::new(
+                    "Install".to_owned(),
+                    vec![Entry::Item(
+                        "CopyFiles".to_owned(),
+                        Value::list_from(&["Files", "Extra"])
+                    )]
+                ),
+            ]
+        );
+
+        let original = inf.clone();
+
+        for (name, err) in [
+            ("", EditError::SectionNameEmpty),
+            (&"a".repeat(256), EditError::SectionNameTooLong),
+            (
+                "Bad]Name",
+                EditError::SectionNameInvalid {
+                    name: "Bad]Name".to_owned(),
+                },
+            ),
+        ] {
+            assert_eq!(inf.set(name, "key", Value::raw("value")), Err(err));
+        }
+
+        assert_eq!(inf, original);
+    }
 
     #[test]
-    fn multiline_value_with_inline_comments() {
+    fn dedup_entries() {
         let buffer = b"\
-            [Section]\n\
-            key = value1,\"value2;not-a-comment\"\\ ; This is an inline comment.\n\
-            ,value3,,value5
+            [Files]\n\
+            driver.sys\n\
+            driver.sys\n\
+            helper.dll\n\
+            [Install]\n\
+            CopyFiles = Files\n\
+            CopyFiles = Files\n\
+            copyfiles = Files\n\
+            CopyFiles = Other\
         ";
-        let inf = Inf::from_bytes(buffer).expect("failed to parse hardcoded INF file");
+        let mut inf = Inf::from_bytes(buffer).expect("failed to parse hardcoded INF file");
+        inf.dedup_entries();
 
         assert_eq!(
             inf.sections(),
-            &vec![Section::new(
-                "Section".to_owned(),
-                vec![Entry::Item(
-                    "key".to_owned(),
-                    Value::List(vec![
-                        "value1".to_owned(),
-                        "value2;not-a-comment".to_owned(),
-                        "value3".to_owned(),
-                        String::new(),
-                        "value5".to_owned()
-                    ]),
-                )]
-            )]
+            &vec![
+                Section::new(
+                    "Files".to_owned(),
+                    vec![
+                        Entry::Value(Value::Raw("driver.sys".to_owned())),
+                        Entry::Value(Value::Raw("helper.dll".to_owned())),
+                    ]
+                ),
+                Section::new(
+                    "Install".to_owned(),
+                    vec![
+                        Entry::Item("CopyFiles".to_owned(), Value::Raw("Files".to_owned())),
+                        Entry::Item("copyfiles".to_owned(), Value::Raw("Files".to_owned())),
+                        Entry::Item("CopyFiles".to_owned(), Value::Raw("Other".to_owned())),
+                    ]
+                ),
+            ]
         );
     }
 
     #[test]
-    fn lines_end_with_crlf() {
-        let buffer = b"\
-            [Version] ; This section is required\r\n\
-            signature = \"$CHICAGO$\"\r\
-        ";
-        let inf = Inf::from_bytes(buffer).expect("failed to parse hardcoded INF file");
+    fn original_casing_is_kept_when_written() {
+        let mut inf =
+            Inf::from_bytes(b"[Version]\nSignature = \"$Windows NT$\"\n[version]\nClass = Net")
+                .expect("failed to parse hardcoded INF file");
+
+        assert_eq!(inf.get("version").map(Section::name), Some("Version"));
+        assert_eq!(inf.get_string("VERSION", "signature"), Some("$Windows NT$"));
+
+        inf.set("VERSION", "CLASS", Value::Raw("Display".to_owned()))
+            .expect("hardcoded section name is valid");
+
+        let mut output = Vec::new();
+        inf.dump(&mut output).expect("writing to a Vec never fails");
 
         assert_eq!(
-            inf.sections(),
-            &vec![Section::new(
-                "Version".to_owned(),
-                vec![Entry::Item(
-                    "signature".to_owned(),
-                    Value::Raw("$CHICAGO$".to_owned())
-                )]
-            )]
+            String::from_utf8(output).unwrap(),
+            "[Version]\nSignature = \"$Windows NT$\"\nClass = \"Display\"\n\n"
         );
     }
 
     #[test]
-    fn multiple_sections() {
+    fn dump_parses_back_to_the_same_inf() {
         let buffer = b"\
-            [Section1]\n\
-            [Section2]\n\
-            [Section3]\
+            [Version]\n\
+            Signature = \"$Windows NT$\"\n\
+            [Strings]\n\
+            Quote = \"He said \"\"hi\"\" ; not a comment\"\n\
+            Path = C:\\Windows\\System32, a,b\n\
+            Percent = 100%%\n\
+            \"weird=key\" = value\n\
+            [Files]\n\
+            driver.sys\n\
+            \"[spaced name].sys\"\n\
+            \"\"\n\
+            [Install]\n\
+            Blank =\n\
+            CopyFiles = Files, ,\"trailing\\\"\n\
+            Path = C:\\\\Windows\\\\System32\\ , \"C:\\a,b\"\n\
+            Multi = \"line one\n\
+            line two\"\n\
+            [Empty]\
         ";
         let inf = Inf::from_bytes(buffer).expect("failed to parse hardcoded INF file");
-
         assert_eq!(
-            inf.sections(),
+            inf.section_names(),
+            vec!["Version", "Strings", "Files", "Install", "Empty"]
+        );
+
+        let mut output = Vec::new();
+        inf.dump(&mut output).expect("writing to a Vec never fails");
+        let dumped = Inf::from_bytes(&output).expect("failed to parse dumped INF file");
+
+        assert_eq!(dumped, inf);
+    }
+
+    #[test]
+    fn dump_writes_empty_and_single_field_values() {
+        let inf = Inf::from_iter([Section::new(
+            "Files".to_owned(),
             vec![
-                Section::new("Section1".to_owned(), vec![]),
-                Section::new("Section2".to_owned(), vec![]),
-                Section::new("Section3".to_owned(), vec![]),
+                Entry::Value(Value::Raw(String::new())),
+                Entry::Value(Value::List(Vec::new())),
+                Entry::Item("Empty".to_owned(), Value::List(Vec::new())),
+                Entry::Item("Single".to_owned(), Value::List(vec!["a".to_owned()])),
+                Entry::Value(Value::List(vec!["b".to_owned()])),
+            ],
+        )]);
+
+        let mut output = Vec::new();
+        inf.dump(&mut output).expect("writing to a Vec never fails");
+        let dumped = Inf::from_bytes(&output).expect("failed to parse dumped INF file");
+
+        assert_eq!(
+            String::from_utf8(output).expect("dumped INF file is UTF-8"),
+            "[Files]\n\"\"\n\"\"\nEmpty = \"\"\nSingle = \"a\"\n\"b\"\n\n"
+        );
+        assert_eq!(
+            dumped.sections()[0].entries(),
+            [
+                Entry::Value(Value::Raw(String::new())),
+                Entry::Value(Value::Raw(String::new())),
+                Entry::Item("Empty".to_owned(), Value::Raw(String::new())),
+                Entry::Item("Single".to_owned(), Value::Raw("a".to_owned())),
+                Entry::Value(Value::Raw("b".to_owned())),
             ]
         );
     }
 
     #[test]
-    fn multiple_entries() {
+    fn to_ini_string_round_trips_items() {
         let buffer = b"\
-            [Section]\n\
-            key1 = value1\n\
-            key2 = value2\n\
-            key3 = value3\
+            [Version]\n\
+            Signature = \"$Windows NT$\"\n\
+            [Files]\n\
+            driver.sys\n\
+            [Install]\n\
+            CopyFiles = Files, Extra\n\
+            AddReg = Install.AddReg\
         ";
         let inf = Inf::from_bytes(buffer).expect("failed to parse hardcoded INF file");
+        let ini = inf.to_ini_string();
 
         assert_eq!(
-            inf.sections(),
-            &vec![Section::new(
-                "Section".to_owned(),
-                vec![
-                    Entry::Item("key1".to_owned(), Value::Raw("value1".to_owned())),
-                    Entry::Item("key2".to_owned(), Value::Raw("value2".to_owned())),
-                    Entry::Item("key3".to_owned(), Value::Raw("value3".to_owned())),
-                ]
-            )]
+            ini,
+            "[Version]\nSignature=$Windows NT$\n\n\
+             [Files]\n\n\
+             [Install]\nCopyFiles=Files,Extra\nAddReg=Install.AddReg\n"
         );
+
+        let exported = Inf::from_str(&ini).expect("failed to parse exported INI file");
+        let keep_items = |inf: &Inf| {
+            inf.sections()
+                .iter()
+                .map(|section| {
+                    let items = section.items().map(|(k, v)| (k.to_owned(), v.clone()));
+                    (section.name().to_owned(), items.collect::<Vec<_>>())
+                })
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(keep_items(&exported), keep_items(&inf));
     }
 
     #[test]
-    fn mixed_entry_kinds() {
+    fn as_ref_sections() {
+        fn section_names(sections: &impl AsRef<[Section]>) -> Vec<&str> {
+            sections.as_ref().iter().map(Section::name).collect()
+        }
+
+        let inf =
+            Inf::from_bytes(b"[Version]\n[Strings]").expect("failed to parse hardcoded INF file");
+
+        assert_eq!(section_names(&inf), vec!["Version", "Strings"]);
+        assert_eq!(section_names(&inf), section_names(&inf.sections()));
+    }
+
+    #[test]
+    fn parse_lenient_recovers_from_errors() {
         let buffer = b"\
-            [Section]\n\
-            value\n\
-            \"value1\",value2,,\"value4\\\"\n\
-            key = value\
+            stray\n\
+            [A]\n\
+            good = 1\n\
+            \"bad\"key = 2\n\
+            also = \"good\"\n\
+            [B]ad]\n\
+            skipped = 3\n\
+            [C]\n\
+            last = 4\
         ";
-        let inf = Inf::from_bytes(buffer).expect("failed to parse hardcoded INF file");
+        let (inf, errors) = Inf::parse_lenient(buffer);
 
         assert_eq!(
             inf.sections(),
-            &vec![Section::new(
-                "Section".to_owned(),
-                vec![
-                    Entry::Value(Value::Raw("value".to_owned())),
-                    Entry::Value(Value::List(vec![
-                        "value1".to_owned(),
-                        "value2".to_owned(),
-                        String::new(),
-                        "value4\\".to_owned()
-                    ])),
-                    Entry::Item("key".to_owned(), Value::Raw("value".to_owned())),
-                ]
-            )]
+            &vec![
+                Section::new(
+                    "A".to_owned(),
+                    vec![
+                        Entry::Item("good".to_owned(), Value::Raw("1".to_owned())),
+                        Entry::Item("also".to_owned(), Value::Raw("good".to_owned())),
+                    ]
+                ),
+                Section::new(
+                    "C".to_owned(),
+                    vec![Entry::Item("last".to_owned(), Value::Raw("4".to_owned()))]
+                ),
+            ]
+        );
+        assert_eq!(
+            errors,
+            vec![
+                ParseError::EntryBeforeSection,
+                ParseError::UnexpectedCharacter {
+                    c: '"',
+                    section: Some("A".to_owned())
+                },
+                ParseError::SectionNameInvalid {
+                    name: "B]ad".to_owned()
+                },
+            ]
         );
+        assert_eq!(Inf::from_bytes(buffer), Err(ParseError::EntryBeforeSection));
     }
 
     #[test]
-    fn item_value_quoted() {
+    fn parse_lenient_resumes_after_unterminated_quote() {
         let buffer = b"\
-            [Section]\n\
-            key = \"value\"\
+            [A]\n\
+            bad = \"unterminated\n\
+            good = 1\n\
+            [B]\n\
+            last = 2\
         ";
-        let inf = Inf::from_bytes(buffer).expect("failed to parse hardcoded INF file");
+        let (inf, errors) = Inf::parse_lenient(buffer);
 
+        assert_eq!(inf.get_string("A", "good"), Some("1"));
+        assert_eq!(inf.get_string("B", "last"), Some("2"));
         assert_eq!(
-            inf.sections(),
-            &vec![Section::new(
-                "Section".to_owned(),
-                vec![Entry::Item(
-                    "key".to_owned(),
-                    Value::Raw("value".to_owned())
-                )]
-            )]
+            errors,
+            vec![ParseError::UnterminatedString {
+                section: Some("A".to_owned())
+            }]
         );
     }
 
     #[test]
-    fn item_value_unquoted() {
+    fn parse_lenient_stops_at_limits() {
+        let options = ParserOptions::new().max_sections(1);
+        let (inf, errors) = Parser::new("[A]\n[]\n[B]\n[C]", options).parse_lenient();
+
+        assert_eq!(inf.sections().len(), 1);
+        assert_eq!(
+            errors,
+            vec![
+                ParseError::SectionNameEmpty,
+                ParseError::LimitExceeded {
+                    limit: ParseLimit::Sections
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn entry_before_first_section() {
         let buffer = b"\
-            [Section]\n\
+            ; Leading comments and blank lines are fine.\n\
+            \n\
             key = value\n\
+            [Section]\
         ";
-        let inf = Inf::from_bytes(buffer).expect("failed to parse hardcoded INF file");
+        let result = Inf::from_bytes(buffer);
 
-        assert_eq!(
-            inf.sections(),
-            &vec![Section::new(
-                "Section".to_owned(),
-                vec![Entry::Item(
-                    "key".to_owned(),
-                    Value::Raw("value".to_owned())
-                )]
-            )]
-        );
+        assert!(matches!(result, Err(ParseError::EntryBeforeSection)));
+        assert!(matches!(
+            InfRef::parse("\u{FEFF}\r\n[Section]"),
+            Ok(inf) if inf.sections().len() == 1
+        ));
     }
 
     #[test]
-    fn item_value_unquoted_with_spaces() {
+    fn malformed_entries_never_panic() {
         let buffer = b"\
             [Section]\n\
-            key = unquoted value with spaces\
+            ,key=value\n\
+            =,\n\
+            a,=\n\
+            ,,\n\
+            [Other] \t \n\
+            k=v\
         ";
-        let inf = Inf::from_bytes(buffer).expect("failed to parse hardcoded INF file");
 
         assert_eq!(
-            inf.sections(),
-            &vec![Section::new(
-                "Section".to_owned(),
-                vec![Entry::Item(
-                    "key".to_owned(),
-                    Value::Raw("unquoted value with spaces".to_owned())
-                )]
-            )]
+            Inf::from_bytes(buffer),
+            Err(ParseError::EmptyKey {
+                section: Some("Section".to_owned())
+            })
+        );
+
+        let (inf, errors) = Inf::parse_lenient(buffer);
+
+        assert_eq!(
+            inf.entries_in("Section").collect::<Vec<_>>(),
+            vec![
+                &Entry::Value(Value::list_from(&["", "key=value"])),
+                &Entry::Value(Value::list_from(&["a", "="])),
+                &Entry::Value(Value::list_from(&["", "", ""])),
+            ]
         );
+        assert_eq!(inf.get_string("Other", "k"), Some("v"));
+        assert_eq!(errors.len(), 1);
     }
 
     #[test]
-    fn item_value_quoted_with_leading_spaces() {
-        let buffer = b"\
-            [Section]\n\
-            key = \"    with 4 leading spaces\"\
-        ";
-        let inf = Inf::from_bytes(buffer).expect("failed to parse hardcoded INF file");
+    fn arbitrary_input_never_panics() {
+        // Short inputs made up of the characters the parser treats specially, generated with a
+        // fixed xorshift seed so that any failure can be reproduced.
+        const ALPHABET: &[u8] = b"[]=,;\"\\\r\n %aZ\xFF";
+        let mut state = 0x2545_F491_4F6C_DD1D_u64;
+        let mut next = || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+
+        for _ in 0..20_000 {
+            let len = usize::try_from(next() % 24).unwrap();
+            let buffer = (0..len)
+                .map(|_| ALPHABET[usize::try_from(next()).unwrap() % ALPHABET.len()])
+                .collect::<Vec<u8>>();
+
+            let result = std::panic::catch_unwind(|| Inf::from_bytes(&buffer).map(|_| ()));
+            assert!(result.is_ok(), "panicked while parsing {buffer:?}");
+
+            for prefix in [b"[S]\n".as_slice(), b"[S]\nkey = ".as_slice()] {
+                let buffer = [prefix, &buffer].concat();
+                let result = std::panic::catch_unwind(|| Inf::from_bytes(&buffer).map(|_| ()));
+                assert!(result.is_ok(), "panicked while parsing {buffer:?}");
+            }
+        }
+    }
 
+    #[test]
+    fn parse_errors_compare_equal() {
         assert_eq!(
-            inf.sections(),
-            &vec![Section::new(
-                "Section".to_owned(),
-                vec![Entry::Item(
-                    "key".to_owned(),
-                    Value::Raw("    with 4 leading spaces".to_owned())
-                )]
-            )]
+            Inf::from_bytes(b"[Section]\nkey = \"value"),
+            Err(ParseError::UnterminatedString {
+                section: Some("Section".to_owned())
+            })
+        );
+        assert_ne!(
+            ParseError::UnterminatedString { section: None },
+            ParseError::UnterminatedString {
+                section: Some("Section".to_owned())
+            }
+        );
+
+        let read_failure = |kind| ParseError::ReadFailure {
+            source: std::io::Error::from(kind),
+        };
+        assert_eq!(
+            read_failure(std::io::ErrorKind::NotFound),
+            read_failure(std::io::ErrorKind::NotFound)
+        );
+        assert_ne!(
+            read_failure(std::io::ErrorKind::NotFound),
+            read_failure(std::io::ErrorKind::PermissionDenied)
         );
     }
 
     #[test]
-    fn item_value_quoted_with_trailing_spaces() {
-        let buffer = b"\
-            [Section]\n\
-            key = \"with 5 trailing spaces     \"\
-        ";
-        let inf = Inf::from_bytes(buffer).expect("failed to parse hardcoded INF file");
+    fn from_path() {
+        let path = std::env::temp_dir().join(format!("inf-from-path-{}.inf", std::process::id()));
+        std::fs::write(&path, b"[Version]\nSignature=\"$Chicago$\"").expect("failed to write file");
+        let result = Inf::from_path(&path);
+        std::fs::remove_file(&path).expect("failed to remove file");
+
+        let inf = result.expect("failed to parse hardcoded INF file");
+        assert_eq!(inf.get_string("Version", "Signature"), Some("$Chicago$"));
 
         assert_eq!(
-            inf.sections(),
-            &vec![Section::new(
-                "Section".to_owned(),
-                vec![Entry::Item(
-                    "key".to_owned(),
-                    Value::Raw("with 5 trailing spaces     ".to_owned())
-                )]
-            )]
+            Inf::from_path(&path),
+            Err(ParseError::ReadFailure {
+                source: std::io::ErrorKind::NotFound.into()
+            })
         );
     }
 
     #[test]
-    fn item_value_quoted_with_equal_sign() {
+    fn from_buf_read_in_one_byte_chunks() {
+        let utf16 = |text: &str| {
+            BOM_LE
+                .into_iter()
+                .chain(text.encode_utf16().flat_map(u16::to_le_bytes))
+                .collect::<Vec<u8>>()
+        };
+        let mut lone_surrogate = utf16("[Strings]\r\nName = \"a");
+        lone_surrogate.extend([0x00, 0xD8, b'b', 0x00, b'"', 0x00, 0x00]);
+
+        let buffers = [
+            utf16("[Strings]\r\nName = \"Caf\u{e9} \u{1F600}\"\r\n[Version]"),
+            lone_surrogate,
+            util::encode_data("[Strings]\r\nName = \"\u{1F600}\"", Encoding::Utf16Be),
+            "[Strings]\nName = \"Caf\u{e9}\"".as_bytes().to_vec(),
+            b"\xFF".to_vec(),
+            b"\xFE".to_vec(),
+            Vec::new(),
+        ];
+
+        for buffer in buffers {
+            let reader = std::io::BufReader::with_capacity(1, buffer.as_slice());
+
+            assert_eq!(Inf::from_buf_read(reader), Inf::from_bytes(&buffer));
+        }
+
+        let buffer = utf16("[A]\n\u{1F600}");
+        let reader = std::io::BufReader::with_capacity(1, buffer.as_slice());
+        let inf = Inf::from_buf_read(reader).expect("failed to parse hardcoded INF file");
+        assert_eq!(
+            inf.entries_in("A").collect::<Vec<_>>(),
+            vec![&Entry::Value(Value::Raw("\u{1F600}".to_owned()))]
+        );
+    }
+
+    #[test]
+    fn skip_to_first_section() {
         let buffer = b"\
-            [Section]\n\
-            \"1+1=2\"
+            PK\x03\x04 junk; = \"\n\
+            more junk\n\
+            [Version]\n\
+            Signature = \"$Chicago$\"\
         ";
-        let inf = Inf::from_bytes(buffer).expect("failed to parse hardcoded INF file");
+
+        assert_eq!(Inf::from_bytes(buffer), Err(ParseError::EntryBeforeSection));
+
+        let options = ParserOptions::new().skip_to_first_section(true);
+        let inf = Inf::from_bytes_with_options(buffer, &options)
+            .expect("failed to parse hardcoded INF file");
+        assert_eq!(inf.section_names(), vec!["Version"]);
+        assert_eq!(inf.get_string("Version", "Signature"), Some("$Chicago$"));
+
+        let inf = Inf::from_bytes_with_options(b"no sections at all", &options)
+            .expect("failed to parse hardcoded INF file");
+        assert_eq!(inf, Inf::default());
+    }
+
+    #[test]
+    fn parse_error_context() {
+        let text = format!(
+            "[{}]\n{}\nkey = \"unterminated\n{}",
+            "A".repeat(50),
+            "é".repeat(50),
+            "[B]"
+        );
+        let err = Parser::new(&text, ParserOptions::default())
+            .parse_with_context()
+            .unwrap_err();
 
         assert_eq!(
-            inf.sections(),
-            &vec![Section::new(
-                "Section".to_owned(),
-                vec![Entry::Value(Value::Raw("1+1=2".to_owned()))]
-            )]
+            err.error(),
+            &ParseError::UnterminatedString {
+                section: Some("A".repeat(50)),
+            }
+        );
+        // The unterminated string runs to the end of the text, so there is nothing after it.
+        assert_eq!(err.offset(), text.len());
+        assert_eq!(
+            err.snippet(),
+            format!("{}\nkey = \"unterminated\n[B]", "é".repeat(16))
         );
+
+        let text = format!("[A]\n = value\n{}", "b".repeat(100));
+        let err = Parser::new(&text, ParserOptions::default())
+            .parse_with_context()
+            .unwrap_err();
+        assert_eq!(err.offset(), "[A]\n = value\n".len());
+        assert_eq!(err.snippet(), format!("[A]\n = value\n{}", "b".repeat(40)));
+
+        let inf = Parser::new("[A]\nkey = value", ParserOptions::default())
+            .parse_with_context()
+            .expect("failed to parse hardcoded INF file");
+        assert_eq!(inf.sections().len(), 1);
+    }
+
+    #[test]
+    fn is_empty() {
+        assert!(Inf::default().is_empty());
+
+        let inf =
+            Inf::from_bytes(b"; only a comment\n").expect("failed to parse hardcoded INF file");
+        assert!(inf.is_empty());
+
+        let inf = Inf::from_bytes(b"[Empty]\n[Full]\nkey = value")
+            .expect("failed to parse hardcoded INF file");
+        assert!(!inf.is_empty());
+        assert!(inf.sections()[0].is_empty());
+        assert!(!inf.sections()[1].is_empty());
     }
 
     #[test]
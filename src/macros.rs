@@ -0,0 +1,139 @@
+/// Builds an [`Inf`](crate::Inf) from sections written inline, such as in tests.
+///
+/// Each section starts with its name in brackets, followed by its entries. Every entry ends
+/// with a semicolon and is either `key = value` or a value on its own. Values are separated by
+/// commas and converted with [`ToString`], so any literal can be used; a single value becomes
+/// a [`Value::Raw`](crate::Value::Raw) and several become a [`Value::List`](crate::Value::List).
+/// Section names and keys may be written as identifiers or as string literals when they
+/// contain other characters. Sections with the same name are merged, as when parsing.
+///
+/// # Examples
+///
+/// ```
+/// use inf::inf;
+///
+/// let inf = inf! {
+///     [Version]
+///     Signature = "$Chicago$";
+///     [DestinationDirs]
+///     DefaultDestDir = 12;
+///     "Files.Copy" = 10, "Fonts";
+///     [Files.Copy]
+///     "driver.sys";
+/// };
+///
+/// assert_eq!(inf.get_string("Version", "Signature"), Some("$Chicago$"));
+/// assert_eq!(inf.get("Files.Copy").unwrap().entries().len(), 1);
+/// ```
+#[macro_export]
+macro_rules! inf {
+    () => {
+        $crate::Inf::default()
+    };
+    ([$($name:tt)+] $($rest:tt)*) => {{
+        let mut sections = ::std::vec::Vec::<(
+            ::std::string::String,
+            ::std::vec::Vec<$crate::Entry>,
+        )>::new();
+        $crate::inf!(@section sections; [$($name)+] $($rest)*);
+
+        sections
+            .into_iter()
+            .map(|(name, entries)| $crate::Section::new(name, entries))
+            .collect::<$crate::Inf>()
+    }};
+
+    (@section $sections:ident;) => {};
+    (@section $sections:ident; [$name:literal] $($rest:tt)*) => {
+        $sections.push((::std::string::ToString::to_string(&$name), ::std::vec::Vec::new()));
+        $crate::inf!(@section $sections; $($rest)*);
+    };
+    (@section $sections:ident; [$($name:tt)+] $($rest:tt)*) => {
+        $sections.push((
+            ::std::string::ToString::to_string(concat!($(stringify!($name)),+)),
+            ::std::vec::Vec::new(),
+        ));
+        $crate::inf!(@section $sections; $($rest)*);
+    };
+    (@section $sections:ident; $key:ident = $($value:expr),+; $($rest:tt)*) => {
+        $crate::inf!(@entry $sections; $crate::Entry::Item(
+            ::std::string::ToString::to_string(stringify!($key)),
+            $crate::inf!(@value $($value),+),
+        ));
+        $crate::inf!(@section $sections; $($rest)*);
+    };
+    (@section $sections:ident; $key:literal = $($value:expr),+; $($rest:tt)*) => {
+        $crate::inf!(@entry $sections; $crate::Entry::Item(
+            ::std::string::ToString::to_string(&$key),
+            $crate::inf!(@value $($value),+),
+        ));
+        $crate::inf!(@section $sections; $($rest)*);
+    };
+    (@section $sections:ident; $($value:expr),+; $($rest:tt)*) => {
+        $crate::inf!(@entry $sections; $crate::Entry::Value($crate::inf!(@value $($value),+)));
+        $crate::inf!(@section $sections; $($rest)*);
+    };
+
+    (@entry $sections:ident; $entry:expr) => {
+        // The first token is always a section header, so there is a section to push to.
+        if let ::std::option::Option::Some((_, entries)) = $sections.last_mut() {
+            entries.push($entry);
+        }
+    };
+
+    (@value $value:expr) => {
+        $crate::Value::Raw(::std::string::ToString::to_string(&$value))
+    };
+    (@value $($value:expr),+) => {
+        $crate::Value::List(::std::vec![$(::std::string::ToString::to_string(&$value)),+])
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Entry, Inf, Section, Value};
+
+    #[test]
+    fn inf_macro() {
+        let inf = inf! {
+            [Version]
+            Signature = "$Chicago$";
+            [DestinationDirs]
+            DefaultDestDir = 12;
+            "Files.Copy" = 10, "Fonts";
+            [Files.Copy]
+            "driver.sys";
+            "helper.dll", 2;
+            ["Version"]
+            Class = "Net";
+        };
+
+        assert_eq!(
+            inf.sections(),
+            &vec![
+                Section::new(
+                    "Version".to_owned(),
+                    vec![
+                        Entry::Item("Signature".to_owned(), Value::Raw("$Chicago$".to_owned())),
+                        Entry::Item("Class".to_owned(), Value::Raw("Net".to_owned())),
+                    ]
+                ),
+                Section::new(
+                    "DestinationDirs".to_owned(),
+                    vec![
+                        Entry::Item("DefaultDestDir".to_owned(), Value::Raw("12".to_owned())),
+                        Entry::Item("Files.Copy".to_owned(), Value::list_from(&["10", "Fonts"])),
+                    ]
+                ),
+                Section::new(
+                    "Files.Copy".to_owned(),
+                    vec![
+                        Entry::Value(Value::Raw("driver.sys".to_owned())),
+                        Entry::Value(Value::list_from(&["helper.dll", "2"])),
+                    ]
+                ),
+            ]
+        );
+        assert_eq!(inf!(), Inf::default());
+    }
+}
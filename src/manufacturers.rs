@@ -0,0 +1,165 @@
+use crate::Inf;
+use crate::section::{Entry, Section};
+
+/// A manufacturer listed in the `[Manufacturer]` section, along with its models.
+///
+/// Each entry has the form `manufacturer-name = models-section[,target-os-version,...]`, or
+/// just `models-section`, in which case the section name doubles as the manufacturer name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Manufacturer {
+    /// The name of the manufacturer, often a `%strkey%` token that has not been expanded.
+    pub name: String,
+    /// The models listed in the models section, followed by those of each decorated
+    /// (e.g. `Models.NTamd64`) section.
+    pub models: Vec<Model>,
+}
+
+/// A device listed in a models section referenced by the `[Manufacturer]` section.
+///
+/// Each entry has the form `device-description = install-section[,hw-id][,compatible-id...]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Model {
+    /// A description of the device, often a `%strkey%` token that has not been expanded.
+    pub description: String,
+    /// The name of the `DDInstall` section used to install the device.
+    pub install_section: String,
+    /// The hardware id of the device.
+    pub hardware_id: Option<String>,
+    /// Compatible ids of the device, in order of preference.
+    pub compatible_ids: Vec<String>,
+}
+
+impl Inf {
+    /// Returns the manufacturers listed in the `[Manufacturer]` section with their models.
+    ///
+    /// Models sections that are referenced but do not exist are treated as empty, and entries
+    /// of a models section without an install section are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use inf::Inf;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let buffer = b"\
+    ///     [Manufacturer]\n\
+    ///     %Contoso% = Contoso,NTamd64\n\
+    ///     [Contoso.NTamd64]\n\
+    ///     %Device% = Device_Install,USB\\VID_0000&PID_0000\
+    /// ";
+    /// let inf = Inf::from_bytes(buffer)?;
+    /// let manufacturers = inf.manufacturers();
+    /// assert_eq!(manufacturers[0].name, "%Contoso%");
+    /// assert_eq!(manufacturers[0].models[0].install_section, "Device_Install");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn manufacturers(&self) -> Vec<Manufacturer> {
+        self.get("Manufacturer")
+            .map(Section::entries)
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|entry| self.resolve_manufacturer(entry))
+            .collect()
+    }
+
+    fn resolve_manufacturer(&self, entry: &Entry) -> Option<Manufacturer> {
+        let (models_section, decorations) = entry.value().as_slice().split_first()?;
+        let name = entry.key().unwrap_or(models_section).to_owned();
+
+        let models = std::iter::once(models_section.clone())
+            .chain(
+                decorations
+                    .iter()
+                    .filter(|decoration| !decoration.is_empty())
+                    .map(|decoration| format!("{models_section}.{decoration}")),
+            )
+            .filter_map(|section| self.get(&section))
+            .flat_map(Section::entries)
+            .filter_map(parse_model)
+            .collect();
+
+        Some(Manufacturer { name, models })
+    }
+}
+
+fn parse_model(entry: &Entry) -> Option<Model> {
+    let description = entry.key()?.to_owned();
+    let fields = entry.value().as_slice();
+    let install_section = fields.first().filter(|field| !field.is_empty())?.clone();
+
+    Some(Model {
+        description,
+        install_section,
+        hardware_id: fields.get(1).filter(|field| !field.is_empty()).cloned(),
+        compatible_ids: fields
+            .iter()
+            .skip(2)
+            .filter(|field| !field.is_empty())
+            .cloned()
+            .collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn manufacturers() {
+        let buffer = b"\
+            [Manufacturer]\n\
+            %Contoso% = Contoso,NTamd64,NTarm64\n\
+            Fabrikam\n\
+            %Missing% = Missing\n\
+            [Contoso]\n\
+            %Device% = Device_Install,PCI\\VEN_1234,PCI\\CC_0300,PCI\\CC_03\n\
+            [contoso.ntamd64]\n\
+            %Device% = Device_Install.x64,PCI\\VEN_1234\n\
+            [Fabrikam]\n\
+            %Widget% = Widget_Install\n\
+            %Broken% = ,ROOT\\BROKEN\
+        ";
+        let inf = Inf::from_bytes(buffer).expect("failed to parse hardcoded INF file");
+
+        assert_eq!(
+            inf.manufacturers(),
+            vec![
+                Manufacturer {
+                    name: "%Contoso%".to_owned(),
+                    models: vec![
+                        Model {
+                            description: "%Device%".to_owned(),
+                            install_section: "Device_Install".to_owned(),
+                            hardware_id: Some("PCI\\VEN_1234".to_owned()),
+                            compatible_ids: vec![
+                                "PCI\\CC_0300".to_owned(),
+                                "PCI\\CC_03".to_owned()
+                            ],
+                        },
+                        Model {
+                            description: "%Device%".to_owned(),
+                            install_section: "Device_Install.x64".to_owned(),
+                            hardware_id: Some("PCI\\VEN_1234".to_owned()),
+                            compatible_ids: vec![],
+                        },
+                    ],
+                },
+                Manufacturer {
+                    name: "Fabrikam".to_owned(),
+                    models: vec![Model {
+                        description: "%Widget%".to_owned(),
+                        install_section: "Widget_Install".to_owned(),
+                        hardware_id: None,
+                        compatible_ids: vec![],
+                    }],
+                },
+                Manufacturer {
+                    name: "%Missing%".to_owned(),
+                    models: vec![],
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,170 @@
+use crate::Inf;
+use crate::section::Section;
+
+impl Inf {
+    /// Returns the sections that `section` pulls in through its `Needs` directives.
+    ///
+    /// `Needs` entries are followed transitively, so the sections needed by a needed section are
+    /// returned as well. Sections are returned once each in breadth-first order, meaning the
+    /// sections `section` needs directly come first, in the order they are listed. `section`
+    /// itself is never returned, even if a reference leads back to it.
+    /// Sections that do not exist in this file are skipped.
+    ///
+    /// `Include` entries name other INF files rather than sections, so they cannot be followed
+    /// within this file; the sections named by `Needs` are often found in those files instead.
+    /// Use [`Inf::resolve_includes`] to get the files to load.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use inf::{Inf, Section};
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let buffer = b"\
+    ///     [Device.NT]\n\
+    ///     Include = machine.inf\n\
+    ///     Needs = Common.NT\n\
+    ///     [Common.NT]\n\
+    ///     Needs = Base, Device.NT\n\
+    ///     [Base]\
+    /// ";
+    /// let inf = Inf::from_bytes(buffer)?;
+    /// let needs = inf.resolve_needs("Device.NT");
+    /// assert_eq!(needs.iter().map(|s| s.name()).collect::<Vec<_>>(), ["Common.NT", "Base"]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn resolve_needs(&self, section: &str) -> Vec<&Section> {
+        let Some(start) = self.get(section) else {
+            return Vec::new();
+        };
+        let mut resolved = vec![start];
+        let mut next = 0;
+
+        // Breadth-first, using `resolved` as the queue so each section is only visited once.
+        while let Some(&current) = resolved.get(next) {
+            next += 1;
+
+            let needs = directive_values(current, "Needs").filter_map(|name| self.get(name));
+
+            for needed in needs {
+                if !resolved.iter().any(|&seen| std::ptr::eq(seen, needed)) {
+                    resolved.push(needed);
+                }
+            }
+        }
+
+        resolved.remove(0);
+        resolved
+    }
+
+    /// Returns the INF files named by the `Include` directives of `section` and of every
+    /// section it needs (see [`Inf::resolve_needs`]).
+    ///
+    /// Files are returned once each, comparing names ignoring ASCII case, in the order they are
+    /// found. They are not loaded; a caller that has them can look up the needed sections that
+    /// are missing from this file in them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use inf::Inf;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let buffer = b"\
+    ///     [Device.NT]\n\
+    ///     Include = machine.inf\n\
+    ///     Needs = Common.NT\n\
+    ///     [Common.NT]\n\
+    ///     Include = pci.inf, Machine.inf\
+    /// ";
+    /// let inf = Inf::from_bytes(buffer)?;
+    /// assert_eq!(inf.resolve_includes("Device.NT"), ["machine.inf", "pci.inf"]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn resolve_includes(&self, section: &str) -> Vec<&str> {
+        let mut files = Vec::<&str>::new();
+        let sections = self
+            .get(section)
+            .into_iter()
+            .chain(self.resolve_needs(section));
+
+        for file in sections.flat_map(|section| directive_values(section, "Include")) {
+            if !file.is_empty() && !files.iter().any(|f| f.eq_ignore_ascii_case(file)) {
+                files.push(file);
+            }
+        }
+
+        files
+    }
+}
+
+/// Returns the fields of every entry of `section` whose key is `directive`, ignoring ASCII case.
+fn directive_values<'a>(section: &'a Section, directive: &str) -> impl Iterator<Item = &'a str> {
+    section
+        .entries()
+        .iter()
+        .filter(move |entry| {
+            entry
+                .key()
+                .is_some_and(|key| key.eq_ignore_ascii_case(directive))
+        })
+        .flat_map(|entry| entry.value().iter())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_needs() {
+        let buffer = b"\
+            [Device.NT]\n\
+            Needs = A, Missing\n\
+            needs = C\n\
+            [A]\n\
+            Needs = B, C\n\
+            [B]\n\
+            Needs = device.nt, A\n\
+            [C]\n\
+            [Unrelated]\
+        ";
+        let inf = Inf::from_bytes(buffer).expect("failed to parse hardcoded INF file");
+        let names = |section: &str| {
+            inf.resolve_needs(section)
+                .into_iter()
+                .map(Section::name)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(names("Device.NT"), vec!["A", "C", "B"]);
+        assert_eq!(names("B"), vec!["Device.NT", "A", "C"]);
+        assert_eq!(names("C"), Vec::<&str>::new());
+        assert_eq!(names("Missing"), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn resolve_includes() {
+        let buffer = b"\
+            [Device.NT]\n\
+            Include = machine.inf\n\
+            Needs = A, Missing\n\
+            [A]\n\
+            include = pci.inf, MACHINE.INF\n\
+            Needs = Device.NT\n\
+            [Unrelated]\n\
+            Include = other.inf\
+        ";
+        let inf = Inf::from_bytes(buffer).expect("failed to parse hardcoded INF file");
+
+        assert_eq!(
+            inf.resolve_includes("device.nt"),
+            vec!["machine.inf", "pci.inf"]
+        );
+        assert_eq!(inf.resolve_includes("A"), vec!["pci.inf", "MACHINE.INF"]);
+        assert_eq!(inf.resolve_includes("Missing"), Vec::<&str>::new());
+    }
+}
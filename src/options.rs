@@ -0,0 +1,237 @@
+/// The code page used to decode INF files that were saved with an ANSI encoding.
+///
+/// Files that begin with a UTF-16 Byte Order Mark are always decoded as UTF-16, regardless of
+/// the code page selected here.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AnsiCodePage {
+    /// UTF-8; invalid sequences are replaced with `U+FFFD`. ASCII-only files decode the same
+    /// under every code page, which is why this is the default.
+    #[default]
+    Utf8,
+    /// Windows-1252 (Western European), the most common code page for legacy INF files.
+    Windows1252,
+    /// Shift-JIS (code page 932), commonly used by Japanese driver packages.
+    ShiftJis,
+    /// ISO-8859-1; every byte maps directly to the Unicode code point of the same value.
+    Latin1,
+}
+
+/// The encoding used to write an INF file back to bytes (see [`util::encode_data`]).
+///
+/// [`util::encode_data`]: crate::util::encode_data
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    /// UTF-16 LE with a Byte Order Mark, the encoding most INF files are saved with.
+    Utf16Le,
+    /// UTF-16 BE with a Byte Order Mark. Windows does not read INF files saved this way, but
+    /// this crate does.
+    Utf16Be,
+    /// An ANSI code page, written without a Byte Order Mark.
+    Ansi(AnsiCodePage),
+}
+
+/// Options that control how an INF file is decoded and parsed.
+///
+/// # Examples
+///
+/// ```
+/// use inf::{AnsiCodePage, Inf, ParserOptions};
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let options = ParserOptions::new().ansi_code_page(AnsiCodePage::Windows1252);
+/// let inf = Inf::from_bytes_with_options(b"[Strings]\nPrice = \"5\x80\"", &options)?;
+/// assert_eq!(inf.sections()[0].name(), "Strings");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+// Each flag is an independent option set through its own builder method.
+#[allow(clippy::struct_excessive_bools)]
+pub struct ParserOptions {
+    pub(crate) ansi_code_page: AnsiCodePage,
+    pub(crate) continuation_char: char,
+    pub(crate) comment_chars: Vec<char>,
+    pub(crate) raw_values: bool,
+    pub(crate) escaped_semicolons: bool,
+    pub(crate) skip_to_first_section: bool,
+    pub(crate) replacement_char: Option<char>,
+    pub(crate) strict_utf16: bool,
+    pub(crate) max_sections: usize,
+    pub(crate) max_entries_per_section: usize,
+    pub(crate) max_value_len: usize,
+    pub(crate) max_continued_lines: usize,
+}
+
+impl Default for ParserOptions {
+    fn default() -> Self {
+        Self {
+            ansi_code_page: AnsiCodePage::default(),
+            continuation_char: '\\',
+            comment_chars: vec![';'],
+            raw_values: false,
+            escaped_semicolons: false,
+            skip_to_first_section: false,
+            replacement_char: Some(char::REPLACEMENT_CHARACTER),
+            strict_utf16: false,
+            // Large driver packages have a few thousand sections and entries; these are meant to
+            // stop pathological input without affecting any real INF file.
+            max_sections: 100_000,
+            max_entries_per_section: 1_000_000,
+            max_value_len: 1024 * 1024,
+            max_continued_lines: 10_000,
+        }
+    }
+}
+
+impl ParserOptions {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the code page used to decode files without a UTF-16 Byte Order Mark.
+    #[must_use]
+    pub fn ansi_code_page(mut self, code_page: AnsiCodePage) -> Self {
+        self.ansi_code_page = code_page;
+        self
+    }
+
+    /// Sets whether a semicolon preceded by a backslash (`\;`) outside of quotes is part of
+    /// the value rather than the start of a comment.
+    ///
+    /// Standard INF files require such values to be quoted instead, so this is disabled by
+    /// default. When enabled, the backslash is removed from the value unless raw values were
+    /// requested (see [`ParserOptions::raw_values`]).
+    #[must_use]
+    pub fn escaped_semicolons(mut self, escaped: bool) -> Self {
+        self.escaped_semicolons = escaped;
+        self
+    }
+
+    /// Sets whether everything before the first `[` is discarded, such as a header that wraps
+    /// the INF text in a larger payload.
+    ///
+    /// Disabled by default, in which case anything other than whitespace and comments before
+    /// the first section is rejected with [`ParseError::EntryBeforeSection`].
+    ///
+    /// [`ParseError::EntryBeforeSection`]: crate::ParseError::EntryBeforeSection
+    #[must_use]
+    pub fn skip_to_first_section(mut self, skip: bool) -> Self {
+        self.skip_to_first_section = skip;
+        self
+    }
+
+    /// Sets the character substituted for invalid code units when decoding UTF-16, or `None`
+    /// to drop them instead.
+    ///
+    /// Defaults to `U+FFFD REPLACEMENT CHARACTER`.
+    #[must_use]
+    pub fn replacement_char(mut self, c: Option<char>) -> Self {
+        self.replacement_char = c;
+        self
+    }
+
+    /// Sets whether invalid UTF-16 is rejected with [`ParseError::InvalidUtf16`] instead of
+    /// being substituted with the replacement character.
+    ///
+    /// Disabled by default. Use [`DecodedText::warnings`] to find where substitutions were made
+    /// without rejecting the file.
+    ///
+    /// [`ParseError::InvalidUtf16`]: crate::ParseError::InvalidUtf16
+    /// [`DecodedText::warnings`]: crate::DecodedText::warnings
+    #[must_use]
+    pub fn strict_utf16(mut self, strict: bool) -> Self {
+        self.strict_utf16 = strict;
+        self
+    }
+
+    /// Sets the character that joins a line with the next one when it ends the line.
+    ///
+    /// Defaults to `\`, as used by standard INF files.
+    #[must_use]
+    pub fn continuation_char(mut self, c: char) -> Self {
+        self.continuation_char = c;
+        self
+    }
+
+    /// Sets the characters that start a comment, which extends to the end of the line.
+    ///
+    /// Defaults to `;`, as used by standard INF files; some INF-derived formats also use `#`.
+    /// As with `;`, these characters are literal within double quotes.
+    /// [`ParserOptions::escaped_semicolons`] only applies to `;`.
+    #[must_use]
+    pub fn comment_chars(mut self, chars: &[char]) -> Self {
+        self.comment_chars = chars.to_vec();
+        self
+    }
+
+    /// Sets the maximum number of distinct sections; sections merged into an earlier one with
+    /// the same name are not counted again.
+    ///
+    /// Defaults to 100,000.
+    #[must_use]
+    pub fn max_sections(mut self, max: usize) -> Self {
+        self.max_sections = max;
+        self
+    }
+
+    /// Sets the maximum number of entries in a section, including those of duplicate sections
+    /// merged into it.
+    ///
+    /// Defaults to 1,000,000.
+    #[must_use]
+    pub fn max_entries_per_section(mut self, max: usize) -> Self {
+        self.max_entries_per_section = max;
+        self
+    }
+
+    /// Sets the maximum length of an entry in bytes, after joining continued lines and
+    /// stripping comments.
+    ///
+    /// Defaults to 1 MiB.
+    #[must_use]
+    pub fn max_value_len(mut self, max: usize) -> Self {
+        self.max_value_len = max;
+        self
+    }
+
+    /// Sets the maximum number of lines that can be joined into a single entry with the
+    /// continuation character.
+    ///
+    /// Unlike [`ParserOptions::max_value_len`], this also stops long runs of lines that
+    /// contain nothing but the continuation character. Defaults to 10,000.
+    #[must_use]
+    pub fn max_continued_lines(mut self, max: usize) -> Self {
+        self.max_continued_lines = max;
+        self
+    }
+
+    pub(crate) fn is_comment_char(&self, c: char) -> bool {
+        self.comment_chars.contains(&c)
+    }
+
+    /// Sets whether values are kept exactly as written, apart from their surrounding quotes.
+    ///
+    /// By default, doubled quotes (`""`) and backslashes (`\\`) within a value are un-escaped
+    /// to a single character, which is what consumers of the value expect but cannot be undone.
+    /// Raw values keep the escaped form so that a writer can reproduce the source text, at the
+    /// cost of every consumer having to un-escape them itself.
+    #[must_use]
+    pub fn raw_values(mut self, raw: bool) -> Self {
+        self.raw_values = raw;
+        self
+    }
+}
+
+/// A limit set through [`ParserOptions`] that was exceeded while parsing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseLimit {
+    /// See [`ParserOptions::max_sections`].
+    Sections,
+    /// See [`ParserOptions::max_entries_per_section`].
+    EntriesPerSection,
+    /// See [`ParserOptions::max_value_len`].
+    ValueLen,
+    /// See [`ParserOptions::max_continued_lines`].
+    ContinuedLines,
+}
@@ -0,0 +1,148 @@
+use crate::Inf;
+use crate::section::Section;
+
+/// Sections that Windows reads by name rather than through a reference from another section.
+const ROOT_SECTIONS: &[&str] = &[
+    "Version",
+    "Manufacturer",
+    "DefaultInstall",
+    "DefaultUninstall",
+    "ClassInstall32",
+    "InterfaceInstall32",
+    "ControlFlags",
+    "DestinationDirs",
+    "SourceDisksNames",
+    "SourceDisksFiles",
+    "SignatureAttributes",
+    "Strings",
+];
+
+impl Inf {
+    /// Returns the names of sections that cannot be reached from the sections Windows reads
+    /// by name, such as `[DefaultInstall]` and `[Manufacturer]`.
+    ///
+    /// Any field of a reachable entry that names a section is taken as a reference to it, which
+    /// covers directives such as `CopyFiles`, `AddReg` and `Needs` as well as the models and
+    /// install sections listed by `[Manufacturer]`. A reference also reaches the decorated
+    /// variants of a section (e.g. `Install` reaches `Install.NTamd64` and
+    /// `Install.NTamd64.Services`). `[Strings]` sections never reference other sections.
+    ///
+    /// Orphan sections are often left over from an earlier version of the file, but may also
+    /// be referenced from another INF file through `Include`/`Needs`. Names are returned in the
+    /// order the sections appear in the file.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use inf::Inf;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let buffer = b"\
+    ///     [DefaultInstall.NTamd64]\n\
+    ///     CopyFiles = Files\n\
+    ///     [Files]\n\
+    ///     driver.sys\n\
+    ///     [OldFiles]\n\
+    ///     legacy.sys\
+    /// ";
+    /// let inf = Inf::from_bytes(buffer)?;
+    /// assert_eq!(inf.validate_no_orphan_sections(), ["OldFiles"]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn validate_no_orphan_sections(&self) -> Vec<&str> {
+        let mut reachable = self
+            .sections()
+            .iter()
+            .filter(|section| {
+                ROOT_SECTIONS
+                    .iter()
+                    .any(|root| is_named_or_decorated(section.name(), root))
+            })
+            .collect::<Vec<_>>();
+        let mut next = 0;
+
+        // Breadth-first, using `reachable` as the queue so each section is only visited once.
+        while let Some(&current) = reachable.get(next) {
+            next += 1;
+
+            if is_named_or_decorated(current.name(), "Strings") {
+                continue;
+            }
+
+            for field in current
+                .entries()
+                .iter()
+                .flat_map(|entry| entry.value().as_slice())
+            {
+                for section in self.sections() {
+                    if is_named_or_decorated(section.name(), field)
+                        && !reachable.iter().any(|&seen| std::ptr::eq(seen, section))
+                    {
+                        reachable.push(section);
+                    }
+                }
+            }
+        }
+
+        self.sections()
+            .iter()
+            .filter(|&section| !reachable.iter().any(|&seen| std::ptr::eq(seen, section)))
+            .map(Section::name)
+            .collect()
+    }
+}
+
+/// Returns whether `name` is `base` or a decorated variant of it (e.g. `base.NTamd64`),
+/// ignoring ASCII case.
+pub(crate) fn is_named_or_decorated(name: &str, base: &str) -> bool {
+    if base.is_empty() {
+        return false;
+    }
+
+    name.get(..base.len()).is_some_and(|prefix| {
+        let rest = &name[base.len()..];
+        prefix.eq_ignore_ascii_case(base) && (rest.is_empty() || rest.starts_with('.'))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn orphan_sections() {
+        let buffer = b"\
+            [Version]\n\
+            Signature = \"$Windows NT$\"\n\
+            [Manufacturer]\n\
+            %Contoso% = Contoso,NTamd64\n\
+            [Contoso.NTamd64]\n\
+            %Device% = Device_Install,PCI\\VEN_1234\n\
+            [Device_Install.NTamd64]\n\
+            CopyFiles = Driver_Files\n\
+            [Device_Install.NTamd64.Services]\n\
+            AddService = contoso,2,Service_Install\n\
+            [Service_Install]\n\
+            ServiceBinary = %12%\\contoso.sys\n\
+            [driver_files]\n\
+            contoso.sys\n\
+            [Unused_Files]\n\
+            old.sys\n\
+            [Strings]\n\
+            Contoso = Unused_Files\
+        ";
+        let inf = Inf::from_bytes(buffer).expect("failed to parse hardcoded INF file");
+
+        assert_eq!(inf.validate_no_orphan_sections(), vec!["Unused_Files"]);
+    }
+
+    #[test]
+    fn named_or_decorated() {
+        assert!(is_named_or_decorated("Install", "install"));
+        assert!(is_named_or_decorated("Install.NTamd64", "Install"));
+        assert!(!is_named_or_decorated("Installer", "Install"));
+        assert!(!is_named_or_decorated("Install", ""));
+    }
+}
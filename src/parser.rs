@@ -1,148 +1,407 @@
-use std::iter::Peekable;
-use std::str::Chars;
+use std::borrow::Cow;
+use std::fmt;
 
-use crate::error::ParseError;
-use crate::section::{Entry, Section};
+use crate::borrowed::{EntryRef, InfRef, SectionRef, ValueRef};
+use crate::error::{ContextualParseError, ParseError};
+use crate::options::{ParseLimit, ParserOptions};
+use crate::section::MAX_NAME_LEN;
 
 /// Represents an on-going parse.
-#[derive(Debug, Clone)]
-pub struct Parser<'a> {
-    // TODO: Change to `&'a str`:
-    //  data: &'a str,
-    //  position: usize,
-    chars: Peekable<Chars<'a>>,
+///
+/// Most callers should use [`Inf`](crate::Inf) or [`InfRef`] directly. The parser is useful
+/// when hooks need to be installed, such as reporting progress through a large file.
+///
+/// `'a` is the lifetime of the text being parsed and `'f` is the lifetime of any hooks.
+///
+/// Whitespace always refers to ASCII whitespace: spaces and tabs around keys and values are
+/// trimmed alike, while other Unicode whitespace is kept as part of the text.
+///
+/// # Examples
+///
+/// ```
+/// use inf::{Parser, ParserOptions};
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut names = Vec::new();
+/// let inf = Parser::new("[Version]\n[Strings]", ParserOptions::default())
+///     .on_section(|name| names.push(name.to_owned()))
+///     .parse()?;
+///
+/// assert_eq!(inf.sections().len(), 2);
+/// assert_eq!(names, ["Version", "Strings"]);
+/// # Ok(())
+/// # }
+/// ```
+pub struct Parser<'a, 'f> {
+    text: &'a str,
+    position: usize,
     // TODO: Track current line number for better error messages.
     //  line: usize,
+    options: ParserOptions,
+    on_section: Option<SectionCallback<'f>>,
+    /// Errors that were recovered from, or `None` if the first error should end the parse.
+    errors: Option<Vec<ParseError>>,
 }
 
-impl<'a> Parser<'a> {
+type SectionCallback<'f> = Box<dyn FnMut(&str) + 'f>;
+
+impl fmt::Debug for Parser<'_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Parser")
+            .field("position", &self.position)
+            .field("options", &self.options)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a, 'f> Parser<'a, 'f> {
     #[must_use]
-    pub fn new(text: &'a str) -> Self {
+    pub fn new(text: &'a str, options: ParserOptions) -> Self {
         Self {
-            chars: text.chars().peekable(),
+            text,
+            position: 0,
+            options,
+            on_section: None,
+            errors: None,
         }
     }
-}
 
-impl Parser<'_> {
-    // Moves `self` because we cannot call this function again after reaching the end of `chars`.
-    // Not a big fan of this, as the name is sort of misleading with how involved this method
-    // actually is.
-    //
-    // TODO: Prefer moving `sections` into caller and use as a helper to extract the sections.
-    pub fn into_sections(mut self) -> Result<Vec<Section>, ParseError> {
-        let mut sections = Vec::<Section>::with_capacity(16);
+    /// Registers a callback that is invoked with the name of each section as it completes.
+    ///
+    /// The callback is invoked once per section header, so a section that appears more than
+    /// once in the file is reported each time.
+    #[must_use]
+    pub fn on_section<F>(mut self, f: F) -> Self
+    where
+        F: FnMut(&str) + 'f,
+    {
+        self.on_section = Some(Box::new(f));
+        self
+    }
+
+    /// Parses the entire text.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ParseError`] if the text is not a valid INF file.
+    pub fn parse(self) -> Result<InfRef<'a>, ParseError> {
+        self.into_sections().map(InfRef::from_sections)
+    }
+
+    /// Parses the entire text, keeping the text around the point where parsing stopped if it
+    /// fails.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ContextualParseError`] if the text is not a valid INF file.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use inf::{ParseError, Parser, ParserOptions};
+    ///
+    /// let text = "[Version]\nSignature = \"$Windows NT$\"\n[Strings]\nName = \"Contoso\n";
+    /// let err = Parser::new(text, ParserOptions::default())
+    ///     .parse_with_context()
+    ///     .unwrap_err();
+    ///
+    /// assert!(matches!(err.error(), ParseError::UnterminatedString { .. }));
+    /// assert!(err.snippet().ends_with("Name = \"Contoso\n"));
+    /// ```
+    pub fn parse_with_context(mut self) -> Result<InfRef<'a>, ContextualParseError> {
+        let mut sections = Vec::<SectionRef<'a>>::with_capacity(16);
+
+        match self.parse_into(&mut sections) {
+            Ok(()) => Ok(InfRef::from_sections(sections)),
+            Err(err) => Err(ContextualParseError::new(err, self.text, self.position)),
+        }
+    }
 
-        while let Some(c) = self.chars.next() {
+    /// Parses the entire text, skipping anything that cannot be parsed instead of stopping at
+    /// the first error.
+    ///
+    /// An entry that cannot be parsed is skipped on its own, while a section whose header
+    /// cannot be parsed is skipped along with its entries. Parsing continues with the next
+    /// line or section, and each error is returned in the order it was found. A quote that is
+    /// never closed only takes the line it was opened on with it, rather than the rest of the
+    /// text.
+    ///
+    /// Exceeding a [`ParseLimit`] still ends the parse, since the limits exist to stop
+    /// pathological input; the sections parsed up to that point are returned along with it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use inf::{ParseError, Parser, ParserOptions};
+    ///
+    /// let text = "[A]\nkey = \"a\"b\nkey = b\n[]\nkey = c\n[B]";
+    /// let (inf, errors) = Parser::new(text, ParserOptions::default()).parse_lenient();
+    ///
+    /// assert_eq!(inf.sections().len(), 2);
+    /// assert_eq!(inf.sections()[0].entries().len(), 1);
+    /// assert_eq!(errors.len(), 2);
+    /// assert_eq!(errors[1], ParseError::SectionNameEmpty);
+    /// ```
+    #[must_use]
+    pub fn parse_lenient(mut self) -> (InfRef<'a>, Vec<ParseError>) {
+        let mut sections = Vec::<SectionRef<'a>>::with_capacity(16);
+        self.errors = Some(Vec::new());
+
+        let result = self.parse_into(&mut sections);
+        let mut errors = self.errors.take().unwrap_or_default();
+
+        if let Err(err) = result {
+            errors.push(err);
+        }
+
+        (InfRef::from_sections(sections), errors)
+    }
+
+    // Moves `self` because we cannot call this function again after reaching the end of `text`.
+    pub(crate) fn into_sections(mut self) -> Result<Vec<SectionRef<'a>>, ParseError> {
+        let mut sections = Vec::<SectionRef<'a>>::with_capacity(16);
+        self.parse_into(&mut sections)?;
+
+        Ok(sections)
+    }
+
+    /// Parses every section in the text into `sections`.
+    fn parse_into(&mut self, sections: &mut Vec<SectionRef<'a>>) -> Result<(), ParseError> {
+        if self.options.skip_to_first_section {
+            self.position = self.text.find('[').unwrap_or(self.text.len());
+        }
+
+        while let Some(c) = self.next_char() {
             match c {
-                ';' => self.skip_comment(),
-                '[' => self.parse_section(&mut sections)?,
-                _ => {}
+                c if self.options.is_comment_char(c) => self.skip_comment(),
+                '[' => {
+                    // Errors within the section's entries are recovered from while parsing it,
+                    // so only an invalid header (or an exceeded limit) ends up here.
+                    if let Err(err) = self.parse_section(sections) {
+                        self.recover(err)?;
+                        self.skip_section();
+                    }
+                }
+                c if c.is_ascii_whitespace() || c == crate::BOM_CHAR => {}
+                // Every entry is consumed along with its section, so anything else can only
+                // appear before the first section header.
+                _ => {
+                    self.recover(ParseError::EntryBeforeSection)?;
+                    _ = self.consume_until('\n');
+                }
             }
         }
 
-        Ok(sections)
+        Ok(())
     }
 
-    /// Read to the end of the line since comments start from ';' and end at '\n'.
+    /// Records `err` and returns `Ok` if the parse is lenient and the error can be recovered
+    /// from, or returns `err` otherwise.
+    fn recover(&mut self, err: ParseError) -> Result<(), ParseError> {
+        match self.errors.as_mut() {
+            Some(errors) if !matches!(err, ParseError::LimitExceeded { .. }) => {
+                errors.push(err);
+                Ok(())
+            }
+            _ => Err(err),
+        }
+    }
+
+    /// Skips the rest of the current line and every line up to the next section header.
+    fn skip_section(&mut self) {
+        if !self.text[..self.position].ends_with('\n') {
+            _ = self.consume_until('\n');
+        }
+
+        while self.peek_char().is_some_and(|c| c != '[') {
+            _ = self.consume_until('\n');
+        }
+    }
+
+    /// Returns the text that has not been parsed yet.
+    fn remaining(&self) -> &'a str {
+        &self.text[self.position..]
+    }
+
+    fn peek_char(&self) -> Option<char> {
+        self.remaining().chars().next()
+    }
+
+    fn next_char(&mut self) -> Option<char> {
+        let c = self.peek_char()?;
+        self.position += c.len_utf8();
+        Some(c)
+    }
+
+    /// Advances past the next occurrence of `c`, or to the end of the text if there is none.
+    /// Returns the text that was skipped over, excluding `c`.
+    fn consume_until(&mut self, c: char) -> &'a str {
+        let remaining = self.remaining();
+
+        if let Some(i) = remaining.find(c) {
+            self.position += i + c.len_utf8();
+            &remaining[..i]
+        } else {
+            self.position = self.text.len();
+            remaining
+        }
+    }
+
+    /// Read to the end of the line since comments start from ';' (or another comment character)
+    /// and end at '\n'.
     fn skip_comment(&mut self) {
-        _ = self.chars.find(|&c| c == '\n');
+        _ = self.consume_until('\n');
     }
 
     /// Read each line until the next section or end of file.
-    fn parse_section(&mut self, sections: &mut Vec<Section>) -> Result<(), ParseError> {
+    fn parse_section(&mut self, sections: &mut Vec<SectionRef<'a>>) -> Result<(), ParseError> {
         let section_name = self.parse_section_name()?;
+        let split_lists = !is_strings_section(section_name);
 
         // Duplicate section names are allowed; the specification states we should merge their entries.
+        // Section names are case-insensitive, but errors always report `section_name` as it was
+        // written in this header rather than the name of the section it is merged into.
         let entries = if let Some(i) = sections
             .iter()
-            .position(|section| section_name == section.name())
+            .position(|section| section_name.eq_ignore_ascii_case(section.name()))
         {
             // If a section with the same name already exists, extend it.
-            sections.get_mut(i).unwrap()
+            let section = sections.get_mut(i).unwrap();
+            section.add_duplicate();
+            section
+        } else if sections.len() >= self.options.max_sections {
+            return Err(ParseError::LimitExceeded {
+                limit: ParseLimit::Sections,
+            });
         } else {
             // Otherwise, create a new section.
-            sections.push(Section::new(section_name, Vec::with_capacity(32)));
+            sections.push(SectionRef::new(
+                Cow::Borrowed(section_name),
+                Vec::with_capacity(32),
+            ));
             sections.last_mut().unwrap()
         };
 
-        while self.chars.peek().is_some_and(|&c| c != '[') {
-            if let Some(line) = self.read_next_entry()? {
-                let entry = parse_section_entry(&line)?;
-                entries.push(entry);
+        while self.peek_char().is_some_and(|c| c != '[') {
+            let line = match self.read_next_entry() {
+                Ok(line) => line,
+                Err(err) => {
+                    self.recover(err.in_section(section_name))?;
+                    continue;
+                }
+            };
+
+            // Blank and comment-only lines yield no entry; only the next header ends the section.
+            if let Some(line) = line {
+                if entries.entries().len() >= self.options.max_entries_per_section {
+                    return Err(ParseError::LimitExceeded {
+                        limit: ParseLimit::EntriesPerSection,
+                    });
+                }
+
+                let entry = match line {
+                    Cow::Borrowed(line) => parse_section_entry(line, split_lists, &self.options),
+                    // The entry cannot borrow from a line that only lives in this loop.
+                    Cow::Owned(line) => parse_section_entry(&line, split_lists, &self.options)
+                        .map(EntryRef::into_static),
+                };
+                match entry {
+                    Ok(entry) => entries.push(entry),
+                    Err(err) => self.recover(err.in_section(section_name))?,
+                }
             }
         }
 
+        if let Some(on_section) = self.on_section.as_mut() {
+            on_section(section_name);
+        }
+
         Ok(())
     }
 
     /// Read the line containing the section name.
-    fn parse_section_name(&mut self) -> Result<String, ParseError> {
-        let section_name = self
-            .chars
-            .by_ref()
-            .take_while(|&c| c != ']')
-            .collect::<String>();
+    fn parse_section_name(&mut self) -> Result<&'a str, ParseError> {
+        let start = self.position;
+        let section_name = self.consume_until(']');
 
         if section_name.is_empty() {
             return Err(ParseError::SectionNameEmpty);
-        } else if section_name.len() > 255 {
+        } else if section_name.len() > MAX_NAME_LEN {
             return Err(ParseError::SectionNameTooLong);
         }
 
         // Strip excess whitespace and inline comments; break the loop after consuming the newline.
-        while let Some(c) = self.chars.next() {
+        while let Some(c) = self.next_char() {
             match c {
-                ';' => {
+                c if self.options.is_comment_char(c) => {
                     self.skip_comment();
                     break;
                 }
                 '\n' => break, // Will also consume any Carriage Returns (\r).
-                c if c.is_ascii_whitespace() => {
-                    assert_ne!(c, '\n', r"\n should have been handled separately");
+                '"' => {
+                    // Quoted text after the header is ignored like whitespace, and is never
+                    // taken for a comment even if it contains a ';'.
+                    loop {
+                        match self.next_char() {
+                            Some('"') => break,
+                            Some('\n') | None => {
+                                return Err(ParseError::UnterminatedString {
+                                    section: Some(section_name.to_owned()),
+                                });
+                            }
+                            Some(_) => {}
+                        }
+                    }
+                }
+                // Newlines are matched above, so this only skips the other whitespace.
+                c if c.is_ascii_whitespace() => {}
+                c => {
+                    // Section names cannot contain brackets, so another ']' on this line means
+                    // the name was cut short at the first one (e.g. `[Sec]tion]`).
+                    let line = &self.text[start..];
+                    let end = line.find(|c| c == '\n' || self.options.is_comment_char(c));
+                    let line = &line[..end.unwrap_or(line.len())];
+
+                    if let Some(end) = line.rfind(']').filter(|&end| end > section_name.len()) {
+                        return Err(ParseError::SectionNameInvalid {
+                            name: line[..end].to_owned(),
+                        });
+                    }
+
+                    return Err(ParseError::UnexpectedCharacter {
+                        c,
+                        section: Some(section_name.to_owned()),
+                    });
                 }
-                c => return Err(ParseError::UnexpectedCharacter { c }),
             }
         }
 
         Ok(section_name)
     }
 
-    /// Read the next entry while flattening Line Continuators (\) and stripping inline comments.
-    fn read_next_entry(&mut self) -> Result<Option<String>, ParseError> {
-        let mut line = String::with_capacity(4096);
-        let mut within_quotes = false;
+    /// Read the next entry while flattening Line Continuators (\ by default) and stripping inline
+    /// comments.
+    ///
+    /// The entry is borrowed from the source text unless it spans multiple lines.
+    fn read_next_entry(&mut self) -> Result<Option<Cow<'a, str>>, ParseError> {
+        let mut line = Cow::Borrowed("");
+        let mut continued_lines = 0_usize;
 
         loop {
-            let current = self
-                .chars
-                .by_ref()
-                .take_while(|&c| {
-                    if c == '"' {
-                        within_quotes = !within_quotes;
-                    }
-
-                    // If within double quotes, consume everything (including newlines).
-                    // TODO: This might be special to the [Strings] section; we are applying it
-                    // here to all sections. Additional research required.
-                    within_quotes || c != '\n'
-                })
-                .collect::<String>();
+            let current = self.read_physical_line()?;
             let mut current = current
                 .strip_suffix('\r')
-                .unwrap_or(current.as_str())
-                .trim_end();
-
-            if within_quotes {
-                return Err(ParseError::UnterminatedString);
-            }
+                .unwrap_or(current)
+                .trim_ascii_end();
+            let mut within_quotes = false;
 
             // Trim inline comments
             for (i, c) in current.char_indices() {
                 match c {
                     '"' => within_quotes = !within_quotes,
-                    ';' if !within_quotes => {
-                        current = current[..i].trim_end();
+                    ';' if self.options.escaped_semicolons && is_escaped(&current[..i]) => {}
+                    c if !within_quotes && self.options.is_comment_char(c) => {
+                        current = current[..i].trim_ascii_end();
                         break;
                     }
                     _ => {}
@@ -150,87 +409,260 @@ impl Parser<'_> {
             }
 
             if within_quotes {
-                return Err(ParseError::UnterminatedString);
+                return Err(ParseError::UnterminatedString { section: None });
             }
 
             // If the line ends with a Line Continuator, strip it and continue to next line.
-            if let Some(s) = current.strip_suffix('\\') {
-                line.push_str(s);
-                continue;
+            // Every quote has been closed by now, so a backslash within quotes is never taken
+            // for one, even when it is the last character before a newline.
+            let continued = current.strip_suffix(self.options.continuation_char);
+
+            let current = continued.unwrap_or(current);
+
+            // Checked before joining so that a long chain of continued lines never grows the
+            // buffer past the limit.
+            if line.len() + current.len() > self.options.max_value_len {
+                return Err(ParseError::LimitExceeded {
+                    limit: ParseLimit::ValueLen,
+                });
             }
 
-            line.push_str(current);
-            break;
+            if line.is_empty() {
+                line = Cow::Borrowed(current);
+            } else {
+                line.to_mut().push_str(current);
+            }
+
+            if continued.is_none() {
+                break;
+            }
+
+            continued_lines += 1;
+
+            if continued_lines > self.options.max_continued_lines {
+                return Err(ParseError::LimitExceeded {
+                    limit: ParseLimit::ContinuedLines,
+                });
+            }
+        }
+
+        // Newlines are only kept within double quotes; normalize them so CRLF and LF files
+        // produce the same value.
+        if line.contains("\r\n") {
+            line = Cow::Owned(line.replace("\r\n", "\n"));
         }
 
         Ok(if line.is_empty() { None } else { Some(line) })
     }
+
+    /// Read up to the next newline that is not within double quotes, consuming the newline.
+    fn read_physical_line(&mut self) -> Result<&'a str, ParseError> {
+        let remaining = self.remaining();
+        let mut within_quotes = false;
+
+        for (i, c) in remaining.char_indices() {
+            match c {
+                '"' => within_quotes = !within_quotes,
+                // If within double quotes, consume everything (including newlines).
+                // TODO: This might be special to the [Strings] section; we are applying it
+                // here to all sections. Additional research required.
+                '\n' if !within_quotes => {
+                    self.position += i + 1;
+                    return Ok(&remaining[..i]);
+                }
+                _ => {}
+            }
+        }
+
+        self.position = self.text.len();
+
+        if within_quotes {
+            // A lenient parse resumes after the line the quote was opened on, so that the rest
+            // of the text is not lost to a single missing quote.
+            if self.errors.is_some()
+                && let Some(i) = remaining.find('\n')
+            {
+                self.position = self.text.len() - remaining.len() + i + 1;
+            }
+
+            return Err(ParseError::UnterminatedString { section: None });
+        }
+
+        Ok(remaining)
+    }
 }
 
-fn parse_section_entry(line: &str) -> Result<Entry, ParseError> {
-    assert!(!line.is_empty());
-    assert!(!line.ends_with('\\'));
-    assert!(!line.contains('\r'));
-    assert!(!line.contains('\n'));
+/// Returns whether `name` is a `[Strings]` section, including localized ones such as
+/// `[Strings.0409]`.
+fn is_strings_section(name: &str) -> bool {
+    name.eq_ignore_ascii_case("Strings")
+        || name
+            .get(.."Strings.".len())
+            .is_some_and(|prefix| prefix.eq_ignore_ascii_case("Strings."))
+}
 
-    let mut values = Vec::<String>::new();
+/// Parses a single entry, splitting its value into a list at each unquoted comma unless
+/// `split_lists` is false.
+///
+/// Every value in a `[Strings]` section is a single string in which commas are literal, so
+/// they are parsed with `split_lists` set to false.
+fn parse_section_entry<'l>(
+    line: &'l str,
+    split_lists: bool,
+    options: &ParserOptions,
+) -> Result<EntryRef<'l>, ParseError> {
+    // NOTE: The line may still contain newlines, but only within double quotes. They are kept
+    // as part of the quoted value.
+    // An empty line would become an empty value, but `read_next_entry` never returns one.
+    debug_assert!(!line.is_empty());
+
+    let mut values = Vec::<Cow<'_, str>>::new();
     let mut within_quotes = false;
-    let mut key = None::<String>;
+    let mut key = None::<Cow<'_, str>>;
     let mut start = 0;
 
     for (i, c) in line.char_indices() {
         match c {
             '"' => within_quotes = !within_quotes,
-            ',' if !within_quotes => {
-                if key.is_some() {
-                    assert_ne!(start, 0, "expected start to be after the equal sign");
-                }
-
-                let value = normalize_value(&line[start..i])?;
+            ',' if !within_quotes && split_lists => {
+                // If there is a key, `start` is already past the equal sign that ended it.
+                let value = normalize_value(&line[start..i], options)?;
                 values.push(value);
                 start = i + 1;
             }
             '=' if !within_quotes => {
-                if !values.is_empty() {
+                if key.is_some() || !values.is_empty() {
                     // I'm not sure if unquoted equal signs are allowed, but since it's not
                     // mentioned anywhere in the documentation, we'll assume it is for now.
                     continue;
                 }
 
-                key = Some(line[start..i].trim().to_owned());
+                key = Some(normalize_key(&line[start..i], options)?);
                 start = i + 1;
             }
             _ => {}
         }
     }
 
-    let last = normalize_value(line[start..].trim())?;
+    // A trailing comma is followed by an empty field, as Windows treats it.
+    let last = normalize_value(line[start..].trim_ascii(), options)?;
     values.push(last);
 
     let value = if values.len() == 1 {
-        values.remove(0).into()
+        ValueRef::Raw(values.remove(0))
     } else {
-        values.into()
+        ValueRef::List(values)
     };
 
     Ok(if let Some(k) = key {
-        Entry::Item(k, value)
+        EntryRef::Item(k, value)
     } else {
-        Entry::Value(value)
+        EntryRef::Value(value)
     })
 }
 
-fn normalize_value(mut value: &str) -> Result<String, ParseError> {
-    value = value.trim();
-    value = match (value.starts_with('"'), value.ends_with('"')) {
-        (true, true) => &value[1..value.len() - 1],
-        (false, false) => value,
-        _ => return Err(ParseError::UnterminatedString),
+/// Trims the ASCII whitespace surrounding `key`, keeping its interior characters verbatim.
+///
+/// A double quote within the key is only allowed when it is escaped by doubling it (`""`). A key
+/// wrapped in double quotes (e.g. `"weird=key"`) is then stripped and un-escaped the same way as
+/// a value, while any other key keeps its escaped quotes as-is.
+///
+/// A key that is empty after trimming is rejected with [`ParseError::EmptyKey`].
+fn normalize_key<'k>(key: &'k str, options: &ParserOptions) -> Result<Cow<'k, str>, ParseError> {
+    let key = key.trim_ascii();
+
+    // An empty key can still be written as `""`, so one that is missing entirely is more
+    // likely a mistake than a value-only entry.
+    if key.is_empty() {
+        return Err(ParseError::EmptyKey { section: None });
+    }
+
+    let quoted = key.len() >= 2 && key.starts_with('"') && key.ends_with('"');
+    let unquoted = if quoted { &key[1..key.len() - 1] } else { key };
+
+    if unquoted.split("\"\"").any(|part| part.contains('"')) {
+        return Err(ParseError::UnexpectedCharacter {
+            c: '"',
+            section: None,
+        });
+    }
+
+    if quoted {
+        normalize_value(key, options)
+    } else {
+        Ok(Cow::Borrowed(key))
+    }
+}
+
+/// Strips the surrounding quotes from `value` and un-escapes it (unless raw values were
+/// requested), only allocating if there was something to un-escape.
+///
+/// Every `\\` is taken to be an escaped backslash, so a path doubled in the file (e.g.
+/// `C:\\Windows`) is read as a single one. Pairs are collapsed from the left without
+/// overlapping, which leaves the last of an odd run as written (`\\\` becomes `\\`). A
+/// backslash that ends an unquoted line has already been taken for a line continuation by
+/// `read_next_entry`, so a value ending in one must be quoted.
+fn normalize_value<'v>(
+    mut value: &'v str,
+    options: &ParserOptions,
+) -> Result<Cow<'v, str>, ParseError> {
+    value = value.trim_ascii();
+    // A quote must both open and close the value; a lone quote at either end is never part of
+    // the value itself.
+    let quoted = match (value.starts_with('"'), value.ends_with('"')) {
+        (true, true) if value.len() >= 2 => true,
+        (false, false) => false,
+        _ => return Err(ParseError::UnterminatedString { section: None }),
     };
-    let value = value.replace("\"\"", "\"").replace("\\\\", "\\");
+
+    if quoted {
+        value = &value[1..value.len() - 1];
+    }
 
     // NOTE: We do not un-escape percent signs here since it will become ambiguous later whether
     // they were supposed to be for string substitution or simply escaped percent signs.
 
-    Ok(value)
+    if options.raw_values {
+        return Ok(Cow::Borrowed(value));
+    }
+
+    // Semicolons within quotes never start a comment, so they are never escaped.
+    Ok(unescape(value, options.escaped_semicolons && !quoted))
+}
+
+/// Returns whether the character following `text` is escaped by the backslashes it ends with.
+///
+/// Backslashes pair up the same way as in `normalize_value`, so only an odd run of them
+/// escapes the character; in `a\\;` the semicolon follows an escaped backslash instead.
+fn is_escaped(text: &str) -> bool {
+    (text.len() - text.trim_end_matches('\\').len()) % 2 == 1
+}
+
+/// Un-doubles the quotes and backslashes of a value whose outer quotes were already stripped,
+/// in a single pass from the left so that each character is only ever part of one pair. If
+/// `semicolons` is set, a backslash that escapes a semicolon (`\;`) is removed as well.
+fn unescape(value: &str, semicolons: bool) -> Cow<'_, str> {
+    let escaped =
+        value.contains("\"\"") || value.contains("\\\\") || (semicolons && value.contains("\\;"));
+
+    if !escaped {
+        return Cow::Borrowed(value);
+    }
+
+    let mut unescaped = String::with_capacity(value.len());
+    let mut chars = value.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if semicolons && c == '\\' && chars.peek() == Some(&';') {
+            continue;
+        }
+
+        unescaped.push(c);
+
+        if matches!(c, '"' | '\\') && chars.peek() == Some(&c) {
+            _ = chars.next();
+        }
+    }
+
+    Cow::Owned(unescaped)
 }
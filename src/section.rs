@@ -1,13 +1,32 @@
-#[derive(Debug, Clone, PartialEq, Eq)]
+use std::cmp::Ordering;
+use std::ops::RangeInclusive;
+
+use crate::guid::Guid;
+
+/// The maximum length of a section name, in bytes.
+pub(crate) const MAX_NAME_LEN: usize = 255;
+
+#[derive(Debug, Clone)]
 pub struct Section {
     name: String,
     entries: Vec<Entry>,
+    duplicates: usize,
 }
 
 impl Section {
+    /// Creates a section from its name and entries.
     #[must_use]
-    pub(crate) fn new(name: String, entries: Vec<Entry>) -> Self {
-        Self { name, entries }
+    pub fn new(name: String, entries: Vec<Entry>) -> Self {
+        Self {
+            name,
+            entries,
+            duplicates: 0,
+        }
+    }
+
+    pub(crate) fn with_duplicates(mut self, duplicates: usize) -> Self {
+        self.duplicates = duplicates;
+        self
     }
 
     #[must_use]
@@ -15,36 +34,676 @@ impl Section {
         &self.name
     }
 
+    /// Returns the number of entries in the section.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the section has no entries.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the name without its decoration, i.e. the part before the first `.` (e.g.
+    /// `DDInstall` for `[DDInstall.NTamd64.Services]`).
+    #[must_use]
+    pub fn base_name(&self) -> &str {
+        self.name
+            .split_once('.')
+            .map_or(self.name.as_str(), |(base, _)| base)
+    }
+
+    /// Returns the decoration of the name, i.e. the part after the first `.` (e.g.
+    /// `NTamd64.Services` for `[DDInstall.NTamd64.Services]`), or `None` if there is none.
+    #[must_use]
+    pub fn decoration(&self) -> Option<&str> {
+        self.name.split_once('.').map(|(_, decoration)| decoration)
+    }
+
     #[must_use]
     pub fn entries(&self) -> &[Entry] {
         &self.entries
     }
 
-    pub(crate) fn push(&mut self, value: Entry) {
-        self.entries.push(value);
+    /// Returns the entry at `index`, counting from the top of the section, or `None` if it is
+    /// out of bounds.
+    #[must_use]
+    pub fn entry(&self, index: usize) -> Option<&Entry> {
+        self.entries.get(index)
+    }
+
+    /// Returns an iterator over the entries in the order they appear, the same as
+    /// `entries().iter()`.
+    pub fn iter(&self) -> std::slice::Iter<'_, Entry> {
+        self.entries.iter()
+    }
+
+    /// Returns the number of additional headers with this name (ignoring ASCII case) that were
+    /// merged into this section while parsing.
+    ///
+    /// The specification allows a section to be split across several headers, but it is often
+    /// a copy-paste mistake, so tools may want to warn about it.
+    #[must_use]
+    pub fn duplicate_count(&self) -> usize {
+        self.duplicates
+    }
+
+    /// Returns the value of the first item whose key matches `key`, ignoring ASCII case.
+    #[must_use]
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.items()
+            .find_map(|(k, value)| key.eq_ignore_ascii_case(k).then_some(value))
+    }
+
+    /// Returns an iterator over the key and value of each [`Entry::Item`], skipping value-only
+    /// entries.
+    pub fn items(&self) -> impl Iterator<Item = (&str, &Value)> {
+        self.entries.iter().filter_map(|entry| match entry {
+            Entry::Item(key, value) => Some((key.as_str(), value)),
+            Entry::Value(_) => None,
+        })
+    }
+
+    /// Returns an iterator over the value of each [`Entry::Value`], skipping items.
+    pub fn values_only(&self) -> impl Iterator<Item = &Value> {
+        self.entries.iter().filter_map(|entry| match entry {
+            Entry::Item(..) => None,
+            Entry::Value(value) => Some(value),
+        })
+    }
+
+    /// Returns the values of every item whose key matches `key`, ignoring ASCII case, in the
+    /// order they appear.
+    pub fn get_all<'a>(&'a self, key: &'a str) -> impl Iterator<Item = &'a Value> {
+        self.items()
+            .filter(move |(k, _)| key.eq_ignore_ascii_case(k))
+            .map(|(_, value)| value)
+    }
+
+    /// Replaces the value of the first item whose key matches `key` (ignoring ASCII case),
+    /// or appends a new item if there is none.
+    ///
+    /// The existing item keeps its position and the casing of its key.
+    pub fn set(&mut self, key: &str, value: Value) {
+        let existing = self.entries.iter_mut().find_map(|entry| match entry {
+            Entry::Item(k, v) if key.eq_ignore_ascii_case(k) => Some(v),
+            _ => None,
+        });
+
+        if let Some(existing) = existing {
+            *existing = value;
+        } else {
+            self.entries.push(Entry::Item(key.to_owned(), value));
+        }
+    }
+
+    /// Removes every entry, keeping the section itself.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Keeps the first `len` entries and removes the rest. Has no effect if the section has
+    /// `len` entries or fewer.
+    pub fn truncate(&mut self, len: usize) {
+        self.entries.truncate(len);
+    }
+
+    pub(crate) fn entries_mut(&mut self) -> &mut [Entry] {
+        &mut self.entries
+    }
+
+    /// Renames the section in place, keeping its entries.
+    ///
+    /// The name is not validated; sections renamed through [`Inf::rename_and_merge`] are
+    /// checked against the limits of an INF file instead.
+    ///
+    /// [`Inf::rename_and_merge`]: crate::Inf::rename_and_merge
+    pub fn rename_to(&mut self, name: String) {
+        self.name = name;
+    }
+
+    /// Removes entries that are exact duplicates of an earlier entry, keeping the first.
+    pub(crate) fn dedup(&mut self) {
+        for entry in std::mem::take(&mut self.entries) {
+            if !self.entries.contains(&entry) {
+                self.entries.push(entry);
+            }
+        }
+    }
+
+    /// Merges the entries of `other` into this section according to `strategy`.
+    pub(crate) fn merge(&mut self, other: Section, strategy: MergeStrategy) {
+        match strategy {
+            MergeStrategy::Append => self.entries.extend(other.entries),
+            MergeStrategy::Override => {
+                for entry in other.entries {
+                    let existing = match entry {
+                        Entry::Item(ref key, _) => self
+                            .entries
+                            .iter_mut()
+                            .find(|e| e.key().is_some_and(|k| k.eq_ignore_ascii_case(key))),
+                        Entry::Value(_) => None,
+                    };
+
+                    match (existing, entry) {
+                        (Some(Entry::Item(_, value)), Entry::Item(_, new_value)) => {
+                            *value = new_value;
+                        }
+                        (_, entry) => self.entries.push(entry),
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Sections are equal if their names and entries are equal; how many headers they were parsed
+/// from is not taken into account.
+impl PartialEq for Section {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && self.entries == other.entries
+    }
+}
+
+impl Eq for Section {}
+
+/// Sections are ordered by name, ignoring ASCII case, so that a `Vec<Section>` can be sorted
+/// alphabetically. Entries are not taken into account, except to break ties between sections
+/// with the same name so that the ordering agrees with `==`.
+impl Ord for Section {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.name
+            .bytes()
+            .map(|b| b.to_ascii_lowercase())
+            .cmp(other.name.bytes().map(|b| b.to_ascii_lowercase()))
+            .then_with(|| self.name.cmp(&other.name))
+            .then_with(|| self.entries.cmp(&other.entries))
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+impl PartialOrd for Section {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'a> IntoIterator for &'a Section {
+    type Item = &'a Entry;
+    type IntoIter = std::slice::Iter<'a, Entry>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// How entries are combined when two sections with the same name are merged.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MergeStrategy {
+    /// Append every entry to the end of the existing section, as the specification requires
+    /// for duplicate sections within a single file. Items with the same key are kept.
+    #[default]
+    Append,
+    /// Replace the value of the first existing item with the same key (ignoring ASCII case),
+    /// keeping its position. Items with new keys and value-only entries are appended.
+    Override,
+}
+
+/// A single line within a section, after joining continued lines and stripping comments.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Entry {
+    /// A `key = value` line.
     Item(String, Value),
+    /// A line without a key, such as a file name in a `CopyFiles` section.
     Value(Value),
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+impl Entry {
+    /// Creates an [`Entry::Item`], converting the key and value as needed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use inf::{Entry, Value};
+    ///
+    /// assert_eq!(
+    ///     Entry::item("Signature", "$Windows NT$"),
+    ///     Entry::Item("Signature".to_owned(), Value::Raw("$Windows NT$".to_owned()))
+    /// );
+    /// ```
+    #[must_use]
+    pub fn item(key: impl Into<String>, value: impl Into<Value>) -> Self {
+        Self::Item(key.into(), value.into())
+    }
+
+    /// Returns the key of an [`Entry::Item`], or `None` for a value-only entry.
+    #[must_use]
+    pub fn key(&self) -> Option<&str> {
+        match self {
+            Self::Item(key, _) => Some(key),
+            Self::Value(_) => None,
+        }
+    }
+
+    /// Returns the value of the entry, regardless of whether it has a key.
+    #[must_use]
+    pub fn value(&self) -> &Value {
+        match self {
+            Self::Item(_, value) | Self::Value(value) => value,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Value {
     Raw(String),
     List(Vec<String>),
 }
 
+impl Value {
+    /// Creates a [`Value::Raw`].
+    #[must_use]
+    pub fn raw(value: impl Into<String>) -> Self {
+        Self::Raw(value.into())
+    }
+
+    /// Creates a [`Value::List`] from each element of `values`.
+    #[must_use]
+    pub fn list<I>(values: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        Self::List(values.into_iter().map(Into::into).collect())
+    }
+
+    /// Creates a [`Value::List`] by copying each string slice in `values`.
+    #[must_use]
+    pub fn list_from(values: &[&str]) -> Self {
+        Self::list(values.iter().copied())
+    }
+
+    /// Returns the string if this is a [`Value::Raw`].
+    #[must_use]
+    pub fn as_raw(&self) -> Option<&str> {
+        match self {
+            Self::Raw(value) => Some(value),
+            Self::List(_) => None,
+        }
+    }
+
+    /// Returns the elements if this is a [`Value::List`].
+    #[must_use]
+    pub fn as_list(&self) -> Option<&[String]> {
+        match self {
+            Self::Raw(_) => None,
+            Self::List(values) => Some(values),
+        }
+    }
+
+    /// Transforms the string of a [`Value::Raw`], or each element of a [`Value::List`], with
+    /// `f`, keeping the same variant.
+    #[must_use]
+    pub fn map<F>(self, mut f: F) -> Self
+    where
+        F: FnMut(String) -> String,
+    {
+        match self {
+            Self::Raw(value) => Self::Raw(f(value)),
+            Self::List(values) => Self::List(values.into_iter().map(f).collect()),
+        }
+    }
+
+    /// Returns an iterator over the elements of a list, or over a [`Value::Raw`] as a single
+    /// element, without allocating.
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.as_slice().iter().map(String::as_str)
+    }
+
+    /// Returns an iterator over each element with its position in the list, such as for
+    /// reporting which field of a directive is malformed. A [`Value::Raw`] yields a single
+    /// element at position 0.
+    pub fn list_enumerated(&self) -> impl Iterator<Item = (usize, &str)> {
+        self.iter().enumerate()
+    }
+
+    /// Returns the elements of a list, or a [`Value::Raw`] as a single-element slice.
+    pub(crate) fn as_slice(&self) -> &[String] {
+        match self {
+            Self::Raw(value) => std::slice::from_ref(value),
+            Self::List(values) => values,
+        }
+    }
+
+    /// Parses a [`Value::Raw`] of the form `start-end` (e.g. `0-15`) as an inclusive range.
+    ///
+    /// Either bound may be negative (e.g. `-5--1`) and may be surrounded by whitespace.
+    /// Returns `None` for a list, a value that is not a range, or a range whose start is
+    /// greater than its end.
+    #[must_use]
+    pub fn as_range(&self) -> Option<RangeInclusive<i64>> {
+        let value = self.as_raw()?.trim();
+        // Skip the first character so that the sign of a negative start is not mistaken for
+        // the separator.
+        let sep = value.get(1..)?.find('-')? + 1;
+        let start = value[..sep].trim().parse::<i64>().ok()?;
+        let end = value[sep + 1..].trim().parse::<i64>().ok()?;
+
+        (start <= end).then_some(start..=end)
+    }
+
+    /// Parses a [`Value::Raw`] of the form `{xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx}` as a GUID.
+    ///
+    /// Hex digits may be upper or lowercase. Returns `None` for a list or a malformed GUID.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use inf::Value;
+    ///
+    /// let value = Value::raw("{4D36E97D-E325-11CE-BFC1-08002BE10318}");
+    /// let guid = value.as_guid().unwrap();
+    ///
+    /// assert_eq!(guid.data1, 0x4d36_e97d);
+    /// assert_eq!(guid.to_string(), "{4d36e97d-e325-11ce-bfc1-08002be10318}");
+    /// assert_eq!(Value::raw("{4d36e97d}").as_guid(), None);
+    /// ```
+    #[must_use]
+    pub fn as_guid(&self) -> Option<Guid> {
+        Guid::parse(self.as_raw()?)
+    }
+
+    /// Splits every element on the first occurrence of `sep` into a `(name, value)` pair.
+    ///
+    /// A [`Value::Raw`] is treated as a single-element list. Both halves are trimmed of
+    /// surrounding whitespace. Returns `None` if any element does not contain `sep`.
+    #[must_use]
+    pub fn as_pairs(&self, sep: char) -> Option<Vec<(String, String)>> {
+        self.as_slice()
+            .iter()
+            .map(|element| {
+                let (name, value) = element.split_once(sep)?;
+                Some((name.trim().to_owned(), value.trim().to_owned()))
+            })
+            .collect()
+    }
+}
+
 impl From<String> for Value {
     fn from(value: String) -> Self {
         Value::Raw(value)
     }
 }
 
+impl From<&str> for Value {
+    fn from(value: &str) -> Self {
+        Value::Raw(value.to_owned())
+    }
+}
+
 impl From<Vec<String>> for Value {
     fn from(value: Vec<String>) -> Self {
         Value::List(value)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entry_accessors() {
+        let item = Entry::Item("key".to_owned(), Value::Raw("value".to_owned()));
+        let value = Entry::Value(Value::List(vec!["a".to_owned(), "b".to_owned()]));
+
+        assert_eq!(item.key(), Some("key"));
+        assert_eq!(item.value().as_raw(), Some("value"));
+        assert_eq!(value.key(), None);
+        assert_eq!(value.value().as_raw(), None);
+        assert_eq!(
+            value.value().as_list(),
+            Some(["a".to_owned(), "b".to_owned()].as_slice())
+        );
+    }
+
+    #[test]
+    fn rename_to() {
+        let entries = vec![Entry::Value(Value::Raw("driver.sys".to_owned()))];
+        let mut section = Section::new("Files".to_owned(), entries.clone());
+        section.rename_to("Files.NTamd64".to_owned());
+
+        assert_eq!(section.name(), "Files.NTamd64");
+        assert_eq!(section.entries(), entries.as_slice());
+    }
+
+    #[test]
+    fn set_replaces_first_item_or_appends() {
+        let mut section = Section::new(
+            "Strings".to_owned(),
+            vec![
+                Entry::Value(Value::Raw("name".to_owned())),
+                Entry::Item("Name".to_owned(), Value::Raw("a".to_owned())),
+                Entry::Item("name".to_owned(), Value::Raw("b".to_owned())),
+            ],
+        );
+        section.set("NAME", Value::Raw("c".to_owned()));
+        section.set("Version", Value::list_from(&["1", "2"]));
+
+        assert_eq!(
+            section.entries(),
+            &[
+                Entry::Value(Value::Raw("name".to_owned())),
+                Entry::Item("Name".to_owned(), Value::Raw("c".to_owned())),
+                Entry::Item("name".to_owned(), Value::Raw("b".to_owned())),
+                Entry::Item("Version".to_owned(), Value::list_from(&["1", "2"])),
+            ]
+        );
+    }
+
+    #[test]
+    fn base_name_and_decoration() {
+        let section = |name: &str| Section::new(name.to_owned(), vec![]);
+
+        assert_eq!(section("Install").base_name(), "Install");
+        assert_eq!(section("Install").decoration(), None);
+        assert_eq!(section("Install.NT").base_name(), "Install");
+        assert_eq!(section("Install.NT").decoration(), Some("NT"));
+        assert_eq!(
+            section("Install.NTamd64.Services").decoration(),
+            Some("NTamd64.Services")
+        );
+        assert_eq!(section("Install.").decoration(), Some(""));
+    }
+
+    #[test]
+    fn clear_and_truncate() {
+        let entries = vec![
+            Entry::Value(Value::Raw("a.sys".to_owned())),
+            Entry::Value(Value::Raw("b.sys".to_owned())),
+            Entry::Value(Value::Raw("c.sys".to_owned())),
+        ];
+        let mut section = Section::new("Files".to_owned(), entries.clone());
+
+        assert_eq!(section.len(), 3);
+
+        section.truncate(5);
+        assert_eq!(section.entries(), entries.as_slice());
+
+        section.truncate(1);
+        assert_eq!(section.entries(), &entries[..1]);
+
+        section.clear();
+        assert_eq!(section.name(), "Files");
+        assert!(section.entries().is_empty());
+        assert!(section.is_empty());
+    }
+
+    #[test]
+    fn sections_sort_by_name_ignoring_case() {
+        let section = |name: &str| Section::new(name.to_owned(), vec![]);
+        let mut sections = ["Version", "strings", "Install", "DestinationDirs"]
+            .into_iter()
+            .map(section)
+            .collect::<Vec<_>>();
+        sections.sort();
+
+        assert_eq!(
+            sections.iter().map(Section::name).collect::<Vec<_>>(),
+            vec!["DestinationDirs", "Install", "strings", "Version"]
+        );
+        assert!(section("ABC") < section("abc"));
+        assert!(
+            section("Files")
+                < Section::new(
+                    "Files".to_owned(),
+                    vec![Entry::Value(Value::Raw("a.sys".to_owned()))]
+                )
+        );
+    }
+
+    #[test]
+    fn items_and_values_only() {
+        let section = Section::new(
+            "Section".to_owned(),
+            vec![
+                Entry::Item("a".to_owned(), Value::Raw("1".to_owned())),
+                Entry::Value(Value::Raw("2".to_owned())),
+                Entry::Item("b".to_owned(), Value::list_from(&["3", "4"])),
+                Entry::Value(Value::list_from(&["5", "6"])),
+            ],
+        );
+
+        assert_eq!(
+            section.items().collect::<Vec<_>>(),
+            vec![
+                ("a", &Value::Raw("1".to_owned())),
+                ("b", &Value::list_from(&["3", "4"])),
+            ]
+        );
+        assert_eq!(
+            section.values_only().collect::<Vec<_>>(),
+            vec![&Value::Raw("2".to_owned()), &Value::list_from(&["5", "6"])]
+        );
+    }
+
+    #[test]
+    fn value_as_pairs() {
+        let value = Value::List(vec!["a:1".to_owned(), "b:2".to_owned()]);
+
+        assert_eq!(
+            value.as_pairs(':'),
+            Some(vec![
+                ("a".to_owned(), "1".to_owned()),
+                ("b".to_owned(), "2".to_owned())
+            ])
+        );
+        assert_eq!(value.as_pairs('='), None);
+        assert_eq!(
+            Value::Raw("c:3".to_owned()).as_pairs(':'),
+            Some(vec![("c".to_owned(), "3".to_owned())])
+        );
+    }
+
+    #[test]
+    fn value_as_range() {
+        let range = |value: &str| Value::Raw(value.to_owned()).as_range();
+
+        assert_eq!(range("0-15"), Some(0..=15));
+        assert_eq!(range(" 3 - 3 "), Some(3..=3));
+        assert_eq!(range("-5--1"), Some(-5..=-1));
+        assert_eq!(range("15-0"), None);
+        assert_eq!(range("15"), None);
+        assert_eq!(range("-15"), None);
+        assert_eq!(range("a-b"), None);
+        assert_eq!(Value::list_from(&["0", "15"]).as_range(), None);
+    }
+
+    #[test]
+    fn value_iter() {
+        assert_eq!(
+            Value::list_from(&["a", "b"]).iter().collect::<Vec<_>>(),
+            vec!["a", "b"]
+        );
+        assert_eq!(
+            Value::Raw("c".to_owned()).iter().collect::<Vec<_>>(),
+            vec!["c"]
+        );
+        assert_eq!(Value::list_from(&[]).iter().count(), 0);
+    }
+
+    #[test]
+    fn value_list_enumerated() {
+        assert_eq!(
+            Value::list_from(&["a", "", "c"])
+                .list_enumerated()
+                .collect::<Vec<_>>(),
+            vec![(0, "a"), (1, ""), (2, "c")]
+        );
+        assert_eq!(
+            Value::Raw("a,b".to_owned())
+                .list_enumerated()
+                .collect::<Vec<_>>(),
+            vec![(0, "a,b")]
+        );
+    }
+
+    #[test]
+    fn value_map() {
+        let uppercase = |value: String| value.to_uppercase();
+
+        assert_eq!(
+            Value::Raw("abc".to_owned()).map(uppercase),
+            Value::Raw("ABC".to_owned())
+        );
+        assert_eq!(
+            Value::list_from(&["a", "b"]).map(uppercase),
+            Value::list_from(&["A", "B"])
+        );
+    }
+
+    #[test]
+    fn value_list_from_slice() {
+        assert_eq!(
+            Value::list_from(&["a", "b"]),
+            Value::List(vec!["a".to_owned(), "b".to_owned()])
+        );
+        assert_eq!(Value::list_from(&[]), Value::List(vec![]));
+    }
+
+    #[test]
+    fn entry_and_value_constructors() {
+        assert_eq!(Value::raw("v"), Value::Raw("v".to_owned()));
+        assert_eq!(Value::raw(String::from("v")), Value::Raw("v".to_owned()));
+        assert_eq!(Value::list(["a", "b"]), Value::list_from(&["a", "b"]));
+        assert_eq!(
+            Value::list(vec![String::from("a")]),
+            Value::List(vec!["a".to_owned()])
+        );
+        assert_eq!(
+            Entry::item("key", "value"),
+            Entry::Item("key".to_owned(), Value::Raw("value".to_owned()))
+        );
+        assert_eq!(
+            Entry::item(String::from("key"), Value::list(["a", "b"])),
+            Entry::Item("key".to_owned(), Value::list_from(&["a", "b"]))
+        );
+    }
+
+    #[test]
+    fn entry_by_index() {
+        let section = Section::new(
+            "Files".to_owned(),
+            vec![
+                Entry::item("key", "value"),
+                Entry::Value(Value::raw("a.sys")),
+            ],
+        );
+
+        assert_eq!(section.entry(0), Some(&Entry::item("key", "value")));
+        assert_eq!(section.entry(1), Some(&Entry::Value(Value::raw("a.sys"))));
+        assert_eq!(section.entry(2), None);
+        assert!(section.iter().eq(section.entries()));
+        assert_eq!((&section).into_iter().count(), 2);
+    }
+}
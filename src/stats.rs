@@ -0,0 +1,99 @@
+use crate::Inf;
+use crate::section::{Entry, Section};
+
+/// Counts of the sections and entries of an INF file, as returned by [`Inf::stats`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InfStats {
+    /// The number of sections, counting sections that were merged together once.
+    pub section_count: usize,
+    /// The number of entries across all sections.
+    pub total_entries: usize,
+    /// The number of entries with a key.
+    pub item_count: usize,
+    /// The number of entries without a key.
+    pub value_only_count: usize,
+    /// The name of the section with the most entries, or `None` if there are no sections.
+    /// If several sections are tied, the first of them is used.
+    pub largest_section: Option<String>,
+}
+
+impl Inf {
+    /// Counts the sections and entries of the file.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use inf::Inf;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let inf = Inf::from_bytes(b"[Version]\nClass = Net\n[Files]\na.sys\nb.sys")?;
+    /// let stats = inf.stats();
+    ///
+    /// assert_eq!(stats.section_count, 2);
+    /// assert_eq!(stats.total_entries, 3);
+    /// assert_eq!(stats.largest_section.as_deref(), Some("Files"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn stats(&self) -> InfStats {
+        let entries = self.sections().iter().flat_map(Section::entries);
+        let item_count = entries
+            .clone()
+            .filter(|entry| matches!(entry, Entry::Item(..)))
+            .count();
+        let total_entries = entries.count();
+
+        // `max_by_key` returns the last maximum, so search from the end to get the first one.
+        let largest_section = self
+            .sections()
+            .iter()
+            .rev()
+            .max_by_key(|section| section.len())
+            .map(|section| section.name().to_owned());
+
+        InfStats {
+            section_count: self.sections().len(),
+            total_entries,
+            item_count,
+            value_only_count: total_entries - item_count,
+            largest_section,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats() {
+        let buffer = b"\
+            [Version]\n\
+            Signature = \"$Windows NT$\"\n\
+            Class = Net\n\
+            [Files]\n\
+            a.sys\n\
+            b.sys\n\
+            [Empty]\n\
+            [files]\n\
+            c.sys\
+        ";
+        let inf = Inf::from_bytes(buffer).expect("failed to parse hardcoded INF file");
+
+        assert_eq!(
+            inf.stats(),
+            InfStats {
+                section_count: 3,
+                total_entries: 5,
+                item_count: 2,
+                value_only_count: 3,
+                largest_section: Some("Files".to_owned()),
+            }
+        );
+
+        let tied = Inf::from_bytes(b"[A]\nx\n[B]\ny").expect("failed to parse hardcoded INF file");
+        assert_eq!(tied.stats().largest_section.as_deref(), Some("A"));
+        assert_eq!(Inf::default().stats(), InfStats::default());
+    }
+}
@@ -1,8 +1,116 @@
 use std::fmt;
 
-use crate::section::{Entry, Section, Value};
+use encoding_rs::EncoderResult;
 
+use crate::options::{AnsiCodePage, Encoding};
+use crate::section::{Section, Value};
+
+/// The maximum number of nested `%strkey%` references that will be followed while expanding.
+const MAX_DEPTH: usize = 32;
+
+/// Replaces every `%strkey%` token in `value` with its matching entry from `strings`.
+///
+/// Keys are compared case-insensitively and `%%` is unescaped to a literal `%`. If the value of
+/// a string key contains `%strkey%` tokens naming other entries of `strings`, they are expanded
+/// as well; any other `%` in the value, including `%%`, is kept as written.
+///
+/// # Errors
+///
+/// Returns [`ExpandVarsError::Unterminated`] if a token is missing its closing `%`,
+/// [`ExpandVarsError::NotFound`] if `strings` has no entry for a token,
+/// [`ExpandVarsError::Cycle`] if a string key refers back to itself, or
+/// [`ExpandVarsError::TooDeep`] if references are nested too deeply.
 pub fn expand_vars(value: &str, strings: &Section) -> Result<String, ExpandVarsError> {
+    expand_vars_with_options(value, strings, &ExpandOptions::default())
+}
+
+/// Like [`expand_vars`], but using `options` to control how malformed tokens are handled.
+///
+/// # Errors
+///
+/// Returns any error returned by [`expand_vars`], except for those `options` allows.
+///
+/// # Examples
+///
+/// ```
+/// use inf::Section;
+/// use inf::util::{ExpandOptions, expand_vars_with_options};
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let strings = Section::new("Strings".to_owned(), Vec::new());
+/// let options = ExpandOptions::new().literal_unterminated(true);
+/// assert_eq!(expand_vars_with_options("100%", &strings, &options)?, "100%");
+/// # Ok(())
+/// # }
+/// ```
+pub fn expand_vars_with_options(
+    value: &str,
+    strings: &Section,
+    options: &ExpandOptions,
+) -> Result<String, ExpandVarsError> {
+    expand(value, strings, options, &mut Vec::new(), &mut |_| {
+        Err(ExpandVarsError::NotFound)
+    })
+}
+
+/// Like [`expand_vars`], but keeps scanning after a token is not found so that every missing
+/// key can be reported at once.
+///
+/// # Errors
+///
+/// Returns [`ExpandVarsError::Missing`] listing each token that `strings` has no entry for,
+/// or any other error returned by [`expand_vars`].
+pub fn expand_vars_all(value: &str, strings: &Section) -> Result<String, ExpandVarsError> {
+    let (result, missing) = expand_vars_lenient(value, strings)?;
+
+    if missing.is_empty() {
+        Ok(result)
+    } else {
+        Err(ExpandVarsError::Missing { keys: missing })
+    }
+}
+
+/// Like [`expand_vars_all`], but returns a best-effort result instead of failing when a token
+/// is not found.
+///
+/// Tokens that were found are substituted as usual, while missing tokens are left in the
+/// result exactly as written (e.g. `%missing%`). The names of the missing tokens are returned
+/// alongside the result in the order they first appear.
+///
+/// # Errors
+///
+/// Returns any error returned by [`expand_vars`] other than [`ExpandVarsError::NotFound`].
+pub fn expand_vars_lenient(
+    value: &str,
+    strings: &Section,
+) -> Result<(String, Vec<String>), ExpandVarsError> {
+    let mut missing = Vec::<String>::new();
+    let options = ExpandOptions::default();
+    let result = expand(value, strings, &options, &mut Vec::new(), &mut |var| {
+        if !missing.iter().any(|key| key == var) {
+            missing.push(var.to_owned());
+        }
+
+        Ok(())
+    })?;
+
+    Ok((result, missing))
+}
+
+/// Expands each token in `value`, calling `on_missing` for each token without a replacement.
+///
+/// If `on_missing` returns `Ok`, the token is kept in the result as it was written. `stack`
+/// holds the (lowercase) keys currently being expanded and is used to detect cycles.
+fn expand<F>(
+    value: &str,
+    strings: &Section,
+    options: &ExpandOptions,
+    stack: &mut Vec<String>,
+    on_missing: &mut F,
+) -> Result<String, ExpandVarsError>
+where
+    F: FnMut(&str) -> Result<(), ExpandVarsError>,
+{
     let mut result = String::with_capacity(value.len());
     let mut chars = value.chars().peekable();
 
@@ -26,37 +134,216 @@ pub fn expand_vars(value: &str, strings: &Section) -> Result<String, ExpandVarsE
             match chars.next() {
                 Some('%') => break,
                 Some(ch) => var.push(ch),
+                None if options.literal_unterminated => {
+                    result.push('%');
+                    result.push_str(&var);
+                    return Ok(result);
+                }
                 None => return Err(ExpandVarsError::Unterminated),
             }
         }
 
-        let var_lowercase = var.to_lowercase();
-
-        let replacement = strings
-            .entries()
-            .iter()
-            .find_map(|entry| match entry {
-                Entry::Item(key, value) if var_lowercase == key.to_lowercase() => match value {
-                    Value::Raw(s) => Some(s.as_str()),
-                    // TODO: [Strings] section is special and should not be allowed to have
-                    // Value::List. Not an urgent problem since we are only reading INF files,
-                    // but this needs to be fixed if we ever want to implement an INF writer.
-                    Value::List(..) => None,
-                },
-                _ => None,
-            })
-            .ok_or(ExpandVarsError::NotFound)?;
+        if let Some(replacement) = find_string(strings, &var) {
+            // The result is pushed as is, so a `%` it contains never pairs with one that
+            // follows the token in `value`.
+            result.push_str(&substitute(&var, replacement, strings, stack)?);
+        } else {
+            on_missing(&var)?;
+            result.push('%');
+            result.push_str(&var);
+            result.push('%');
+        }
+    }
+
+    Ok(result)
+}
+
+/// Expands the tokens within `replacement`, the value of the string key `var`.
+fn substitute(
+    var: &str,
+    replacement: &str,
+    strings: &Section,
+    stack: &mut Vec<String>,
+) -> Result<String, ExpandVarsError> {
+    let var_lowercase = var.to_lowercase();
+
+    if stack.contains(&var_lowercase) {
+        return Err(ExpandVarsError::Cycle {
+            key: var.to_owned(),
+        });
+    } else if stack.len() >= MAX_DEPTH {
+        return Err(ExpandVarsError::TooDeep);
+    }
+
+    stack.push(var_lowercase);
+    let result = expand_nested(replacement, strings, stack);
+    stack.pop();
+
+    result
+}
+
+/// Expands the `%strkey%` tokens in the value of a string key that name another entry of
+/// `strings`, keeping every other `%` (including `%%`) as written.
+///
+/// Unlike the text passed to [`expand_vars`], a string value is not written with expansion in
+/// mind, so a stray `%` such as the one in `50%` is not an error.
+fn expand_nested(
+    value: &str,
+    strings: &Section,
+    stack: &mut Vec<String>,
+) -> Result<String, ExpandVarsError> {
+    let mut result = String::with_capacity(value.len());
+    let mut rest = value;
+
+    while let Some(i) = rest.find('%') {
+        result.push_str(&rest[..i]);
+        let after = &rest[i + 1..];
 
-        result.push_str(replacement);
+        if let Some(after_escape) = after.strip_prefix('%') {
+            result.push_str("%%");
+            rest = after_escape;
+            continue;
+        }
+
+        let token = after.find('%').and_then(|end| {
+            find_string(strings, &after[..end]).map(|replacement| (end, replacement))
+        });
+
+        if let Some((end, replacement)) = token {
+            result.push_str(&substitute(&after[..end], replacement, strings, stack)?);
+            rest = &after[end + 1..];
+        } else {
+            result.push('%');
+            rest = after;
+        }
     }
 
+    result.push_str(rest);
     Ok(result)
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Returns the value of the string key `var` from `strings`, ignoring case.
+fn find_string<'a>(strings: &'a Section, var: &str) -> Option<&'a str> {
+    let var_lowercase = var.to_lowercase();
+
+    strings.items().find_map(|(key, value)| {
+        if var_lowercase != key.to_lowercase() {
+            return None;
+        }
+
+        match value {
+            Value::Raw(s) => Some(s.as_str()),
+            // The parser never splits values in a [Strings] section, so a list can only come
+            // from a section built by hand.
+            Value::List(..) => None,
+        }
+    })
+}
+
+/// Encodes `text` with `encoding`, adding a Byte Order Mark for UTF-16.
+///
+/// This is the inverse of the decoding done by [`Inf::from_bytes_with_options`], so that text
+/// written by [`Inf::dump`] can be saved in the encoding of the original file. Characters
+/// outside the Basic Multilingual Plane are written as surrogate pairs in UTF-16. An ANSI
+/// code page cannot represent every character, so those it cannot are written as `?`.
+///
+/// [`Inf::from_bytes_with_options`]: crate::Inf::from_bytes_with_options
+/// [`Inf::dump`]: crate::Inf::dump
+///
+/// # Examples
+///
+/// ```
+/// use inf::util::encode_data;
+/// use inf::{Encoding, Inf};
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let bytes = encode_data("[Version]", Encoding::Utf16Le);
+/// assert_eq!(&bytes[..4], b"\xFF\xFE[\x00");
+/// assert!(Inf::from_bytes(&bytes)?.has_section("Version"));
+/// # Ok(())
+/// # }
+/// ```
+#[must_use]
+pub fn encode_data(text: &str, encoding: Encoding) -> Vec<u8> {
+    match encoding {
+        Encoding::Utf16Le => crate::BOM_LE
+            .into_iter()
+            .chain(text.encode_utf16().flat_map(u16::to_le_bytes))
+            .collect(),
+        Encoding::Utf16Be => crate::BOM_BE
+            .into_iter()
+            .chain(text.encode_utf16().flat_map(u16::to_be_bytes))
+            .collect(),
+        Encoding::Ansi(AnsiCodePage::Utf8) => text.as_bytes().to_vec(),
+        Encoding::Ansi(AnsiCodePage::Latin1) => text
+            .chars()
+            .map(|c| u8::try_from(c).unwrap_or(b'?'))
+            .collect(),
+        Encoding::Ansi(AnsiCodePage::Windows1252) => encode_ansi(text, encoding_rs::WINDOWS_1252),
+        Encoding::Ansi(AnsiCodePage::ShiftJis) => encode_ansi(text, encoding_rs::SHIFT_JIS),
+    }
+}
+
+/// Encodes `text` with a multi-byte capable code page, writing `?` for unmappable characters
+/// rather than the HTML character references `encoding_rs` substitutes by default.
+fn encode_ansi(text: &str, encoding: &'static encoding_rs::Encoding) -> Vec<u8> {
+    let mut encoder = encoding.new_encoder();
+    let mut bytes = Vec::with_capacity(text.len());
+    let mut remaining = text;
+
+    loop {
+        let (result, read) =
+            encoder.encode_from_utf8_to_vec_without_replacement(remaining, &mut bytes, true);
+        remaining = &remaining[read..];
+
+        match result {
+            EncoderResult::InputEmpty => return bytes,
+            EncoderResult::OutputFull => bytes.reserve(remaining.len().max(16)),
+            EncoderResult::Unmappable(_) => bytes.push(b'?'),
+        }
+    }
+}
+
+/// Options that control how `%strkey%` tokens are expanded.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExpandOptions {
+    pub(crate) literal_unterminated: bool,
+}
+
+impl ExpandOptions {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether a `%` that is never closed is kept as a literal `%` instead of failing
+    /// with [`ExpandVarsError::Unterminated`], such as the one in `100%`.
+    ///
+    /// Disabled by default.
+    #[must_use]
+    pub fn literal_unterminated(mut self, literal: bool) -> Self {
+        self.literal_unterminated = literal;
+        self
+    }
+}
+
+/// An error returned when a value cannot be expanded.
+///
+/// This is not `Copy`, because some variants carry the names of the string keys involved;
+/// use `clone` where a copy was made before.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ExpandVarsError {
     Unterminated,
     NotFound,
+    /// Each token that has no entry, in the order they first appear.
+    Missing {
+        keys: Vec<String>,
+    },
+    /// The string key whose value refers back to itself.
+    Cycle {
+        key: String,
+    },
+    TooDeep,
 }
 
 impl std::error::Error for ExpandVarsError {
@@ -70,6 +357,9 @@ impl fmt::Display for ExpandVarsError {
         match *self {
             Self::Unterminated => "unterminated %strkey% sequence".fmt(f),
             Self::NotFound => "string key not found".fmt(f),
+            Self::Missing { ref keys } => write!(f, "string keys not found: {}", keys.join(", ")),
+            Self::Cycle { ref key } => write!(f, "string key forms a reference cycle: {key}"),
+            Self::TooDeep => write!(f, "string keys nested deeper than {MAX_DEPTH} levels"),
         }
     }
 }
@@ -77,6 +367,7 @@ impl fmt::Display for ExpandVarsError {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::section::Entry;
 
     #[test]
     fn expand() {
@@ -133,4 +424,156 @@ mod tests {
 
         assert!(matches!(result, Err(ExpandVarsError::Unterminated)));
     }
+
+    #[test]
+    fn literal_unterminated() {
+        let strings = Section::new(
+            "Strings".to_owned(),
+            vec![
+                Entry::Item("rate".to_owned(), Value::Raw("50%".to_owned())),
+                Entry::Item("name".to_owned(), Value::Raw("Stinky".to_owned())),
+            ],
+        );
+        let options = ExpandOptions::new().literal_unterminated(true);
+        let expand = |value| expand_vars_with_options(value, &strings, &options);
+
+        assert_eq!(expand("%name% is 100%"), Ok("Stinky is 100%".to_owned()));
+        assert_eq!(expand("%name"), Ok("%name".to_owned()));
+        assert_eq!(expand("%rate% off"), Ok("50% off".to_owned()));
+        assert_eq!(expand("%missing%"), Err(ExpandVarsError::NotFound));
+        assert_eq!(
+            expand_vars("%rate% off", &strings),
+            Ok("50% off".to_owned())
+        );
+    }
+
+    #[test]
+    fn encode() {
+        let text = "[Strings]\nName = \"Caf\u{e9} \u{20ac}5 \u{1F600}\"";
+
+        for encoding in [
+            Encoding::Utf16Le,
+            Encoding::Utf16Be,
+            Encoding::Ansi(AnsiCodePage::Utf8),
+        ] {
+            let inf = crate::Inf::from_bytes(&encode_data(text, encoding))
+                .expect("failed to parse encoded INF file");
+            assert_eq!(
+                inf.get_string("Strings", "Name"),
+                Some("Caf\u{e9} \u{20ac}5 \u{1F600}")
+            );
+        }
+
+        assert_eq!(
+            encode_data("A\u{1F600}", Encoding::Utf16Le),
+            [0xFF, 0xFE, b'A', 0x00, 0x3D, 0xD8, 0x00, 0xDE]
+        );
+        assert_eq!(
+            encode_data("A\u{1F600}", Encoding::Utf16Be),
+            [0xFE, 0xFF, 0x00, b'A', 0xD8, 0x3D, 0xDE, 0x00]
+        );
+        assert_eq!(
+            encode_data(
+                "\u{e9}\u{20ac}\u{1F600}",
+                Encoding::Ansi(AnsiCodePage::Windows1252)
+            ),
+            [0xE9, 0x80, b'?']
+        );
+        assert_eq!(
+            encode_data("\u{e9}\u{20ac}", Encoding::Ansi(AnsiCodePage::Latin1)),
+            [0xE9, b'?']
+        );
+        assert_eq!(
+            encode_data("\u{3042}\u{e9}", Encoding::Ansi(AnsiCodePage::ShiftJis)),
+            [0x82, 0xA0, b'?']
+        );
+    }
+
+    #[test]
+    fn expand_all_reports_every_missing_key() {
+        let strings = Section::new(
+            "Strings".to_owned(),
+            vec![Entry::Item(
+                "found".to_owned(),
+                Value::Raw("yes".to_owned()),
+            )],
+        );
+        let result = expand_vars_all("%first% %found% %second% %first%", &strings);
+
+        assert_eq!(
+            result,
+            Err(ExpandVarsError::Missing {
+                keys: vec!["first".to_owned(), "second".to_owned()]
+            })
+        );
+    }
+
+    #[test]
+    fn expand_lenient_keeps_missing_tokens() {
+        let strings = Section::new(
+            "Strings".to_owned(),
+            vec![Entry::Item(
+                "found".to_owned(),
+                Value::Raw("yes".to_owned()),
+            )],
+        );
+        let (expanded, missing) = expand_vars_lenient("%found%, %missing%!", &strings)
+            .expect("expected hardcoded string to be valid");
+
+        assert_eq!(expanded, "yes, %missing%!".to_owned());
+        assert_eq!(missing, vec!["missing".to_owned()]);
+    }
+
+    #[test]
+    fn nested_expands() {
+        let strings = Section::new(
+            "Strings".to_owned(),
+            vec![
+                Entry::Item(
+                    "greeting".to_owned(),
+                    Value::Raw("Hello, %name%!".to_owned()),
+                ),
+                Entry::Item("name".to_owned(), Value::Raw("Stinky".to_owned())),
+            ],
+        );
+
+        let expanded = expand_vars("%greeting% %name%", &strings)
+            .expect("expected hardcoded string to be valid");
+
+        assert_eq!(expanded, "Hello, Stinky! Stinky".to_owned());
+    }
+
+    #[test]
+    fn escaped_percent_in_replacement_is_kept_verbatim() {
+        let strings = Section::new(
+            "Strings".to_owned(),
+            vec![
+                Entry::Item("path".to_owned(), Value::Raw("C:\\100%%".to_owned())),
+                Entry::Item("name".to_owned(), Value::Raw("Stinky".to_owned())),
+            ],
+        );
+
+        // The `%%` in the value of `path` is pushed as written rather than un-escaped, and does
+        // not pair with the `%` after `name`; only the `%%` in the input becomes a single `%`.
+        let expanded =
+            expand_vars("%path%name%%", &strings).expect("expected hardcoded string to be valid");
+
+        assert_eq!(expanded, "C:\\100%%name%".to_owned());
+    }
+
+    #[test]
+    fn self_referential_strkey() {
+        let strings = Section::new(
+            "Strings".to_owned(),
+            vec![Entry::Item("a".to_owned(), Value::Raw("%a%".to_owned()))],
+        );
+        let result = expand_vars("%A%", &strings);
+
+        assert_eq!(
+            result,
+            Err(ExpandVarsError::Cycle {
+                key: "a".to_owned()
+            })
+        );
+    }
 }
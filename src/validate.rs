@@ -0,0 +1,173 @@
+use std::fmt;
+
+use crate::Inf;
+
+/// Options that control which warnings [`Inf::validate_with_options`] reports.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationOptions {
+    pub(crate) max_value_len: usize,
+}
+
+impl Default for ValidationOptions {
+    fn default() -> Self {
+        Self {
+            // The longest string Windows accepts for a field, after substituting string keys.
+            max_value_len: 4096,
+        }
+    }
+}
+
+impl ValidationOptions {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum length of a field in characters; each field of a list is measured on
+    /// its own.
+    ///
+    /// Defaults to 4096. Unlike [`ParserOptions::max_value_len`], exceeding this is only
+    /// reported as a [`ValidationWarning::ValueTooLong`] and does not stop the file from being
+    /// read.
+    ///
+    /// [`ParserOptions::max_value_len`]: crate::ParserOptions::max_value_len
+    #[must_use]
+    pub fn max_value_len(mut self, max: usize) -> Self {
+        self.max_value_len = max;
+        self
+    }
+}
+
+/// A problem found by [`Inf::validate`] that Windows may reject, but that does not stop the
+/// file from being read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationWarning {
+    /// A field is longer than [`ValidationOptions::max_value_len`].
+    ValueTooLong {
+        section: String,
+        /// The key of the entry, or `None` for a value-only entry.
+        key: Option<String>,
+        /// The length of the field in characters.
+        len: usize,
+    },
+}
+
+impl fmt::Display for ValidationWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::ValueTooLong {
+                ref section,
+                ref key,
+                len,
+            } => {
+                write!(f, "value is {len} characters long")?;
+
+                if let Some(key) = key {
+                    write!(f, " for key {key:?}")?;
+                }
+
+                write!(f, " in section [{section}]")
+            }
+        }
+    }
+}
+
+impl Inf {
+    /// Checks the file for problems that Windows may reject, using the default
+    /// [`ValidationOptions`].
+    ///
+    /// Warnings are returned in the order they appear in the file.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use inf::{Inf, ValidationWarning};
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let buffer = format!("[Strings]\nLong = \"{}\"", "x".repeat(5000));
+    /// let inf = Inf::from_bytes(buffer.as_bytes())?;
+    ///
+    /// assert_eq!(
+    ///     inf.validate(),
+    ///     [ValidationWarning::ValueTooLong {
+    ///         section: "Strings".to_owned(),
+    ///         key: Some("Long".to_owned()),
+    ///         len: 5000,
+    ///     }]
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn validate(&self) -> Vec<ValidationWarning> {
+        self.validate_with_options(&ValidationOptions::default())
+    }
+
+    /// Like [`Inf::validate`], but using `options` to control which warnings are reported.
+    #[must_use]
+    pub fn validate_with_options(&self, options: &ValidationOptions) -> Vec<ValidationWarning> {
+        let mut warnings = Vec::new();
+
+        for section in self.sections() {
+            for entry in section.entries() {
+                for field in entry.value().iter() {
+                    let len = field.chars().count();
+
+                    if len > options.max_value_len {
+                        warnings.push(ValidationWarning::ValueTooLong {
+                            section: section.name().to_owned(),
+                            key: entry.key().map(str::to_owned),
+                            len,
+                        });
+                    }
+                }
+            }
+        }
+
+        warnings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn value_too_long() {
+        let buffer = "\
+            [Files]\n\
+            short.sys\n\
+            a_long_file_name.sys,é_long_file_name.sys\n\
+            [Install]\n\
+            CopyFiles = Files, Long_Section_Name\
+        ";
+        let inf = Inf::from_bytes(buffer.as_bytes()).expect("failed to parse hardcoded INF file");
+        let options = ValidationOptions::new().max_value_len(16);
+
+        assert!(inf.validate().is_empty());
+        assert_eq!(
+            inf.validate_with_options(&options),
+            vec![
+                ValidationWarning::ValueTooLong {
+                    section: "Files".to_owned(),
+                    key: None,
+                    len: 20,
+                },
+                ValidationWarning::ValueTooLong {
+                    section: "Files".to_owned(),
+                    key: None,
+                    len: 20,
+                },
+                ValidationWarning::ValueTooLong {
+                    section: "Install".to_owned(),
+                    key: Some("CopyFiles".to_owned()),
+                    len: 17,
+                },
+            ]
+        );
+        assert_eq!(
+            inf.validate_with_options(&options)[2].to_string(),
+            "value is 17 characters long for key \"CopyFiles\" in section [Install]"
+        );
+    }
+}
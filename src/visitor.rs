@@ -0,0 +1,121 @@
+use crate::Inf;
+use crate::section::{Entry, Value};
+
+/// Receives the sections and entries of an [`Inf`] as they are walked by [`Inf::accept`].
+///
+/// Every method does nothing by default, so implementors only need to handle the callbacks
+/// they are interested in. Entries always belong to the section most recently passed to
+/// [`Visitor::section`].
+///
+/// # Examples
+///
+/// ```
+/// use inf::{Inf, Value, Visitor};
+///
+/// #[derive(Default)]
+/// struct Signature<'a> {
+///     in_version: bool,
+///     signature: Option<&'a str>,
+/// }
+///
+/// impl<'a> Visitor<'a> for Signature<'a> {
+///     fn section(&mut self, name: &'a str) {
+///         self.in_version = name.eq_ignore_ascii_case("Version");
+///     }
+///
+///     fn item(&mut self, key: &'a str, value: &'a Value) {
+///         if self.in_version && key.eq_ignore_ascii_case("Signature") {
+///             self.signature = value.as_raw();
+///         }
+///     }
+/// }
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let inf = Inf::from_bytes(b"[Version]\nSignature=\"$Chicago$\"")?;
+/// let mut visitor = Signature::default();
+/// inf.accept(&mut visitor);
+/// assert_eq!(visitor.signature, Some("$Chicago$"));
+/// # Ok(())
+/// # }
+/// ```
+pub trait Visitor<'a> {
+    /// Called at the start of each section, before any of its entries.
+    fn section(&mut self, name: &'a str) {
+        _ = name;
+    }
+
+    /// Called for each entry with a key (`key = value`).
+    fn item(&mut self, key: &'a str, value: &'a Value) {
+        _ = (key, value);
+    }
+
+    /// Called for each entry without a key.
+    fn value_only(&mut self, value: &'a Value) {
+        _ = value;
+    }
+}
+
+impl Inf {
+    /// Walks every section and entry in file order, passing each to `visitor`.
+    pub fn accept<'a, V: Visitor<'a>>(&'a self, visitor: &mut V) {
+        for section in self.sections() {
+            visitor.section(section.name());
+
+            for entry in section.entries() {
+                match entry {
+                    Entry::Item(key, value) => visitor.item(key, value),
+                    Entry::Value(value) => visitor.value_only(value),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct Recorder {
+        events: Vec<String>,
+    }
+
+    impl Visitor<'_> for Recorder {
+        fn section(&mut self, name: &str) {
+            self.events.push(format!("[{name}]"));
+        }
+
+        fn item(&mut self, key: &str, value: &Value) {
+            self.events.push(format!("{key} = {value:?}"));
+        }
+
+        fn value_only(&mut self, value: &Value) {
+            self.events.push(format!("{value:?}"));
+        }
+    }
+
+    #[test]
+    fn accept_visits_in_file_order() {
+        let buffer = b"\
+            [Version]\n\
+            Signature = \"$Chicago$\"\n\
+            [Files]\n\
+            driver.sys\n\
+            [Empty]\
+        ";
+        let inf = Inf::from_bytes(buffer).expect("failed to parse hardcoded INF file");
+        let mut recorder = Recorder::default();
+        inf.accept(&mut recorder);
+
+        assert_eq!(
+            recorder.events,
+            vec![
+                "[Version]".to_owned(),
+                "Signature = Raw(\"$Chicago$\")".to_owned(),
+                "[Files]".to_owned(),
+                "Raw(\"driver.sys\")".to_owned(),
+                "[Empty]".to_owned(),
+            ]
+        );
+    }
+}